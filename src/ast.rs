@@ -1,17 +1,56 @@
 // src/ast.rs
 
+use crate::span::Span;
+
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub span: Span,
+}
+
+impl Expression {
+    pub fn new(kind: ExpressionKind, span: Span) -> Self {
+        Expression { kind, span }
+    }
+}
+
 #[derive(Debug, Clone)]
-pub enum Expression {
+pub enum ExpressionKind {
     Integer(i64),
     Float(f64),
     String(String),
     Boolean(bool),
     Identifier(String),
     BinaryOp(Box<Expression>, BinOp, Box<Expression>),
-    None
+    /// `nhi <expr>`
+    Not(Box<Expression>),
+    /// `chalao <name> <args> par`
+    Call(String, Vec<Expression>),
+    /// `banao jo le <params> fir <body> aage`
+    Function(Vec<String>, Vec<Statement>),
+    /// `suchi <items> aage`
+    List(Vec<Expression>),
+    /// `kosh <key> <value> ... aage`
+    Kosh(Vec<(Expression, Expression)>),
+    /// `naya <dhancha> <field values> aage`
+    Record(String, Vec<Expression>),
+    /// `<record> ka <field>`
+    Field(Box<Expression>, String),
+    /// `<value> ki lambai`
+    Length(Box<Expression>),
+    /// A string with `{...}` parts, evaluated each time it is used.
+    Interpolated(Vec<StringPart>),
 }
 
 #[derive(Debug, Clone)]
+pub enum StringPart {
+    Text(String),
+    Expression(Expression),
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum BinOp {
     Plus,
     Minus,
@@ -26,25 +65,138 @@ pub enum BinOp {
     NotEquals,
     And,
     Or,
+    /// `<list> <index> ka tatva`, or `<kosh> <key> ka tatva`
+    Index,
+    /// `<value> <collection> me hai`
+    Contains,
+}
+
+impl fmt::Display for BinOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            BinOp::Plus => "ka yog",
+            BinOp::Minus => "ka antar",
+            BinOp::Multiply => "ka guna",
+            BinOp::Divide => "ka bhaag",
+            BinOp::Modulo => "%",
+            BinOp::LessThan => "se chota hai",
+            BinOp::GreaterThan => "se bada hai",
+            BinOp::LessThanOrEqual => "se chota hai ya barabar hai",
+            BinOp::GreaterThanOrEqual => "se bada hai ya barabar hai",
+            BinOp::Equals => "hai",
+            BinOp::NotEquals => "nhi hai",
+            BinOp::And => "aur",
+            BinOp::Or => "ya",
+            BinOp::Index => "ka tatva",
+            BinOp::Contains => "me hai",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// What a `jab` case of `milao` matches. Both hold literals.
+#[derive(Debug, Clone)]
+pub enum Case {
+    /// `jab <value>`
+    Value(Expression),
+    /// `jab <start> se <end> tak`, including both ends.
+    Range(Expression, Expression),
+}
+
+#[derive(Debug, Clone)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+impl Statement {
+    pub fn new(kind: StatementKind, span: Span) -> Self {
+        Statement { kind, span }
+    }
 }
 
 #[derive(Debug, Clone)]
-pub enum Statement {
+pub enum StatementKind {
     Declaration(String, Expression),
+    /// `sthir <name> barabar <value>`, which [`crate::checker`] makes sure
+    /// is never changed.
+    Constant(String, Expression),
     Assignment(String, Expression),
+    /// `badlo <list> <index> ka tatva barabar <value>`; on a kosh this
+    /// inserts or replaces the key.
+    IndexAssignment {
+        list: Expression,
+        index: Expression,
+        value: Expression,
+    },
+    /// `badlo <record> ka <field> barabar <value>`
+    FieldAssignment {
+        record: Expression,
+        field: String,
+        value: Expression,
+    },
+    /// `dhancha <name> <fields> aage`
+    StructDef(String, Vec<String>),
+    /// `jodo <list> me <value>`
+    Append {
+        list: Expression,
+        value: Expression,
+    },
+    /// `hatao <list> se <index>`, or `hatao <kosh> se <key>`
+    Remove {
+        list: Expression,
+        index: Expression,
+    },
     If {
         condition: Expression,
         then_block: Vec<Statement>,
         else_block: Vec<Statement>,
     },
+    /// `milao <value> jab <case> fir ... [warna ...] aage`, running the
+    /// body of the first case that matches, or `default` if none does.
+    Match {
+        value: Expression,
+        cases: Vec<(Case, Vec<Statement>)>,
+        default: Vec<Statement>,
+    },
     While {
         condition: Expression,
         body: Vec<Statement>,
     },
+    /// `har <variable> <start> se <end> tak [kadam <step>]`, counting up to
+    /// and including `end`.
+    For {
+        variable: String,
+        start: Expression,
+        end: Expression,
+        step: Option<Expression>,
+        body: Vec<Statement>,
+    },
+    /// `har <variable> <list> me`, running the body for each element, or
+    /// for each key of a kosh.
+    ForEach {
+        variable: String,
+        iterable: Expression,
+        body: Vec<Statement>,
+    },
+    Break,
+    Continue,
+    /// `koshish <body> pakdo <variable> <handler> aage`
+    Try {
+        body: Vec<Statement>,
+        variable: String,
+        handler: Vec<Statement>,
+    },
+    /// `fenko <value>`
+    Throw(Expression),
     Print(Expression),
     Input(String, String),
-    FunctionDef (String, Vec<Expression>, Vec<Statement>, Expression ),
-    FunctionCall (String, Vec<Expression>, String )
+    FunctionDef(String, Vec<String>, Vec<Statement>),
+    /// `wapas karo [value]`
+    Return(Option<Expression>),
+    FunctionCall(String, Vec<Expression>, String),
+    /// An expression evaluated for its side effects, like a bare `chalao`.
+    Expression(Expression),
 }
 
 #[derive(Debug)]
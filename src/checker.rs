@@ -0,0 +1,381 @@
+// src/checker.rs
+
+use crate::ast::*;
+use crate::parser::ParseError;
+use crate::span::Span;
+
+/// Reports every statement in `program` that changes a `sthir` constant
+/// or declares its name again while it is in scope.
+///
+/// This runs on the whole program after parsing, because a function can
+/// be written before the constants it uses: its body runs later, when
+/// every name declared in the blocks around it may already exist.
+pub(crate) fn check(program: &Program) -> Vec<ParseError> {
+    let mut checker = Checker {
+        scopes: Vec::new(),
+        complete: 0,
+        errors: Vec::new(),
+    };
+    checker.block(&[], Span::default(), &program.statements);
+    checker.errors
+}
+
+#[derive(Clone)]
+struct Binding {
+    name: String,
+    /// The statement that declares the name.
+    span: Span,
+    constant: bool,
+}
+
+struct Scope {
+    /// Every name the block declares, wherever in the block.
+    all: Vec<Binding>,
+    /// The names declared by the statements checked so far.
+    declared: Vec<Binding>,
+}
+
+struct Checker {
+    scopes: Vec<Scope>,
+    /// Scopes below this index enclose the function being checked. Its
+    /// body only runs once it is called, so every name they declare is
+    /// visible to it, not just the ones written above it.
+    complete: usize,
+    errors: Vec<ParseError>,
+}
+
+impl Checker {
+    /// Checks `statements` in a new scope that starts out with `names`, the
+    /// parameters or loop variable of the statement at `span`.
+    fn block(&mut self, names: &[String], span: Span, statements: &[Statement]) {
+        let names: Vec<Binding> = names
+            .iter()
+            .map(|name| Binding {
+                name: name.clone(),
+                span,
+                constant: false,
+            })
+            .collect();
+        for binding in &names {
+            self.check_shadow(&binding.name, span);
+        }
+
+        let mut all = names.clone();
+        all.extend(statements.iter().filter_map(declaration));
+        self.scopes.push(Scope {
+            all,
+            declared: names,
+        });
+        for statement in statements {
+            self.statement(statement);
+        }
+        self.scopes.pop();
+    }
+
+    fn function(&mut self, params: &[String], span: Span, body: &[Statement]) {
+        let complete = std::mem::replace(&mut self.complete, self.scopes.len());
+        self.block(params, span, body);
+        self.complete = complete;
+    }
+
+    /// The binding that `name` refers to at this point.
+    fn lookup(&self, name: &str) -> Option<&Binding> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, scope)| {
+                let bindings = if index < self.complete {
+                    &scope.all
+                } else {
+                    &scope.declared
+                };
+                bindings.iter().find(|binding| binding.name == name)
+            })
+    }
+
+    fn check_shadow(&mut self, name: &str, span: Span) {
+        if let Some(binding) = self.lookup(name).filter(|binding| binding.constant) {
+            self.errors.push(ParseError::ConstantRedeclared {
+                name: name.to_string(),
+                constant: binding.span,
+                span,
+            });
+        }
+    }
+
+    fn check_assignment(&mut self, name: &str, span: Span) {
+        if let Some(binding) = self.lookup(name).filter(|binding| binding.constant) {
+            self.errors.push(ParseError::ConstantAssignment {
+                name: name.to_string(),
+                constant: binding.span,
+                span,
+            });
+        }
+    }
+
+    fn declare(&mut self, name: &str, span: Span, constant: bool) {
+        self.check_shadow(name, span);
+        self.scopes.last_mut().unwrap().declared.push(Binding {
+            name: name.to_string(),
+            span,
+            constant,
+        });
+    }
+
+    /// `padho x` and `chalao ... x me` overwrite the nearest `x`, and
+    /// declare it in the current block if there is none.
+    fn store(&mut self, name: &str, span: Span) {
+        if self.lookup(name).is_some() {
+            self.check_assignment(name, span);
+        } else {
+            self.declare(name, span, false);
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        let span = statement.span;
+        match &statement.kind {
+            StatementKind::Declaration(name, value) => {
+                self.expression(value);
+                self.declare(name, span, false);
+            }
+            StatementKind::Constant(name, value) => {
+                self.expression(value);
+                self.declare(name, span, true);
+            }
+            StatementKind::Assignment(name, value) => {
+                self.expression(value);
+                self.check_assignment(name, span);
+            }
+            StatementKind::IndexAssignment { list, index, value } => {
+                self.expression(list);
+                self.expression(index);
+                self.expression(value);
+            }
+            StatementKind::FieldAssignment { record, value, .. } => {
+                self.expression(record);
+                self.expression(value);
+            }
+            StatementKind::StructDef(..) | StatementKind::Break | StatementKind::Continue => {}
+            StatementKind::Append { list, value } => {
+                self.expression(list);
+                self.expression(value);
+            }
+            StatementKind::Remove { list, index } => {
+                self.expression(list);
+                self.expression(index);
+            }
+            StatementKind::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                self.expression(condition);
+                self.block(&[], span, then_block);
+                self.block(&[], span, else_block);
+            }
+            StatementKind::Match {
+                value,
+                cases,
+                default,
+            } => {
+                self.expression(value);
+                for (case, body) in cases {
+                    match case {
+                        Case::Value(value) => self.expression(value),
+                        Case::Range(start, end) => {
+                            self.expression(start);
+                            self.expression(end);
+                        }
+                    }
+                    self.block(&[], span, body);
+                }
+                self.block(&[], span, default);
+            }
+            StatementKind::While { condition, body } => {
+                self.expression(condition);
+                self.block(&[], span, body);
+            }
+            StatementKind::For {
+                variable,
+                start,
+                end,
+                step,
+                body,
+            } => {
+                self.expression(start);
+                self.expression(end);
+                if let Some(step) = step {
+                    self.expression(step);
+                }
+                self.block(std::slice::from_ref(variable), span, body);
+            }
+            StatementKind::ForEach {
+                variable,
+                iterable,
+                body,
+            } => {
+                self.expression(iterable);
+                self.block(std::slice::from_ref(variable), span, body);
+            }
+            StatementKind::Try {
+                body,
+                variable,
+                handler,
+            } => {
+                self.block(&[], span, body);
+                self.block(std::slice::from_ref(variable), span, handler);
+            }
+            StatementKind::Throw(value) | StatementKind::Print(value) => self.expression(value),
+            StatementKind::Return(value) => {
+                if let Some(value) = value {
+                    self.expression(value);
+                }
+            }
+            StatementKind::Expression(value) => self.expression(value),
+            StatementKind::Input(_, name) => self.store(name, span),
+            StatementKind::FunctionDef(name, params, body) => {
+                self.declare(name, span, false);
+                self.function(params, span, body);
+            }
+            StatementKind::FunctionCall(_, args, target) => {
+                for arg in args {
+                    self.expression(arg);
+                }
+                self.store(target, span);
+            }
+        }
+    }
+
+    /// Checks the bodies of any anonymous functions inside `expr`.
+    fn expression(&mut self, expr: &Expression) {
+        match &expr.kind {
+            ExpressionKind::Function(params, body) => self.function(params, expr.span, body),
+            ExpressionKind::BinaryOp(lhs, _, rhs) => {
+                self.expression(lhs);
+                self.expression(rhs);
+            }
+            ExpressionKind::Not(value)
+            | ExpressionKind::Field(value, _)
+            | ExpressionKind::Length(value) => self.expression(value),
+            ExpressionKind::Call(_, items)
+            | ExpressionKind::List(items)
+            | ExpressionKind::Record(_, items) => {
+                for item in items {
+                    self.expression(item);
+                }
+            }
+            ExpressionKind::Kosh(entries) => {
+                for (key, value) in entries {
+                    self.expression(key);
+                    self.expression(value);
+                }
+            }
+            ExpressionKind::Interpolated(parts) => {
+                for part in parts {
+                    if let StringPart::Expression(part) = part {
+                        self.expression(part);
+                    }
+                }
+            }
+            ExpressionKind::Integer(_)
+            | ExpressionKind::Float(_)
+            | ExpressionKind::String(_)
+            | ExpressionKind::Boolean(_)
+            | ExpressionKind::Identifier(_) => {}
+        }
+    }
+}
+
+/// The name a statement declares in its own block, if any.
+fn declaration(statement: &Statement) -> Option<Binding> {
+    let (name, constant) = match &statement.kind {
+        StatementKind::Constant(name, _) => (name, true),
+        StatementKind::Declaration(name, _)
+        | StatementKind::FunctionDef(name, ..)
+        | StatementKind::Input(_, name)
+        | StatementKind::FunctionCall(_, _, name) => (name, false),
+        _ => return None,
+    };
+    Some(Binding {
+        name: name.clone(),
+        span: statement.span,
+        constant,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{ParseError, Parser};
+    use crate::tokens::Token;
+
+    fn check(source: &str) -> Vec<ParseError> {
+        let tokens = Token::tokenize(source).expect("source should lex");
+        Parser::new(&tokens).parse().err().unwrap_or_default()
+    }
+
+    #[test]
+    fn function_written_before_a_constant_cannot_change_it() {
+        let errors = check(
+            "banao badal jo le x fir badlo PI barabar 4 wapas karo x aage\n\
+             sthir PI barabar 3\n\
+             chalao badal 1 par",
+        );
+        assert!(matches!(
+            &errors[..],
+            [ParseError::ConstantAssignment { name, .. }] if name == "PI"
+        ));
+    }
+
+    #[test]
+    fn function_cannot_reuse_the_name_of_a_constant() {
+        let errors = check("sthir PI barabar 3\nbanao PI jo le x fir wapas karo x aage");
+        assert!(matches!(
+            &errors[..],
+            [ParseError::ConstantRedeclared { .. }]
+        ));
+    }
+
+    #[test]
+    fn padho_cannot_overwrite_a_constant() {
+        let errors = check("sthir x barabar 3\npadho sankhya x");
+        assert!(matches!(
+            &errors[..],
+            [ParseError::ConstantAssignment { .. }]
+        ));
+    }
+
+    #[test]
+    fn padho_in_a_block_cannot_overwrite_an_outer_constant() {
+        let errors = check("sthir x barabar 3\nagar satya padho sankhya x aage");
+        assert!(matches!(
+            &errors[..],
+            [ParseError::ConstantAssignment { .. }]
+        ));
+    }
+
+    #[test]
+    fn parameters_and_loop_variables_cannot_shadow_a_constant() {
+        let errors = check(
+            "sthir x barabar 3\n\
+             banao f jo le x fir wapas karo x aage\n\
+             har x 1 se 2 tak likho x aage",
+        );
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|error| matches!(error, ParseError::ConstantRedeclared { .. })));
+    }
+
+    #[test]
+    fn constants_may_shadow_outer_variables() {
+        let errors = check(
+            "mano x barabar 1\n\
+             agar x 1 hai sthir x barabar 2 likho x aage\n\
+             banao f jo le y fir sthir k barabar y wapas karo k aage\n\
+             likho chalao f 1 par",
+        );
+        assert!(errors.is_empty());
+    }
+}
@@ -0,0 +1,688 @@
+// src/diagnostics.rs
+
+use crate::interpreter::{RuntimeError, MAX_CALL_DEPTH};
+use crate::messages::{self, Language};
+use crate::parser::{Expected, ParseError, ParseWarning};
+use crate::span::Span;
+use crate::tokens::{LexError, LexErrorKind, Token};
+
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem in a source file, ready to be rendered with the offending
+/// source line underlined.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub span: Span,
+    pub label: Option<String>,
+    /// Other places worth pointing at, underlined with `-`.
+    pub secondary: Vec<(Span, String)>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            code,
+            message: message.into(),
+            span,
+            label: None,
+            secondary: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    pub fn warning(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(code, message, span)
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_secondary(mut self, span: Span, label: impl Into<String>) -> Self {
+        self.secondary.push((span, label.into()));
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Renders the diagnostic in the style of rustc:
+    ///
+    /// ```text
+    /// error[E0201]: expected `barabar`, found `likho`
+    ///  --> main.bhasha:3:8
+    ///   |
+    /// 3 | mano x likho
+    ///   |        ^^^^^ expected `barabar`
+    /// ```
+    pub fn render(&self, source: &str, filename: &str, color: bool, lang: Language) -> String {
+        let paint = Painter { color };
+        let lines: Vec<&str> = source.split('\n').collect();
+
+        let mut labels: Vec<(Span, Option<&str>, bool)> =
+            vec![(self.span, self.label.as_deref(), true)];
+        for (span, label) in &self.secondary {
+            labels.push((*span, Some(label.as_str()), false));
+        }
+        labels.sort_by_key(|(span, _, _)| span.start);
+
+        let max_line = labels
+            .iter()
+            .map(|(span, _, _)| span.line)
+            .max()
+            .unwrap_or(1);
+        let width = max_line.to_string().len();
+        let gutter = " ".repeat(width);
+        let bar = paint.blue("|");
+
+        let (kind, kind_color): (&str, fn(&Painter, &str) -> String) = match self.severity {
+            Severity::Error => (messages::text(lang, "error"), Painter::red),
+            Severity::Warning => (messages::text(lang, "warning"), Painter::yellow),
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "{}{}",
+            kind_color(&paint, &format!("{}[{}]", kind, self.code)),
+            paint.bold(&format!(": {}", self.message))
+        );
+        let _ = writeln!(
+            out,
+            "{}{} {}:{}:{}",
+            gutter,
+            paint.blue("-->"),
+            filename,
+            self.span.line,
+            self.span.column
+        );
+        let _ = writeln!(out, "{} {}", gutter, bar);
+
+        let mut previous_line: Option<usize> = None;
+        for (span, label, primary) in labels {
+            let text = lines
+                .get(span.line - 1)
+                .copied()
+                .unwrap_or("")
+                .trim_end_matches('\r');
+
+            if let Some(previous) = previous_line {
+                if span.line > previous + 1 {
+                    let _ = writeln!(out, "{}", paint.blue("..."));
+                }
+            }
+
+            if previous_line != Some(span.line) {
+                let _ = writeln!(
+                    out,
+                    "{} {} {}",
+                    paint.blue(&format!("{:>width$}", span.line, width = width)),
+                    bar,
+                    text
+                );
+            }
+            previous_line = Some(span.line);
+
+            // Keep tabs in the padding so the underline lines up with the text.
+            let padding: String = text
+                .chars()
+                .take(span.column - 1)
+                .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+                .collect();
+            let underline_len = source
+                .get(span.start..span.end)
+                .map(|slice| slice.split('\n').next().unwrap_or("").chars().count())
+                .unwrap_or(0)
+                .max(1);
+            let (marker, marker_color): (char, fn(&Painter, &str) -> String) = if primary {
+                ('^', kind_color)
+            } else {
+                ('-', Painter::blue)
+            };
+            let mut underline = marker.to_string().repeat(underline_len);
+            if let Some(label) = label {
+                underline.push(' ');
+                underline.push_str(label);
+            }
+            let _ = writeln!(
+                out,
+                "{} {} {}{}",
+                gutter,
+                bar,
+                padding,
+                marker_color(&paint, &underline)
+            );
+        }
+
+        if !self.notes.is_empty() || self.help.is_some() {
+            let _ = writeln!(out, "{} {}", gutter, bar);
+        }
+        let note = paint.bold(messages::text(lang, "note"));
+        for text in &self.notes {
+            let _ = writeln!(out, "{} {} {}: {}", gutter, paint.blue("="), note, text);
+        }
+        if let Some(text) = &self.help {
+            let help = paint.bold(messages::text(lang, "help"));
+            let _ = writeln!(out, "{} {} {}: {}", gutter, paint.blue("="), help, text);
+        }
+
+        out
+    }
+}
+
+/// Wraps text in ANSI colour codes when colour output is enabled.
+struct Painter {
+    color: bool,
+}
+
+impl Painter {
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn bold(&self, text: &str) -> String {
+        self.paint("1", text)
+    }
+
+    fn red(&self, text: &str) -> String {
+        self.paint("1;31", text)
+    }
+
+    fn yellow(&self, text: &str) -> String {
+        self.paint("1;33", text)
+    }
+
+    fn blue(&self, text: &str) -> String {
+        self.paint("1;34", text)
+    }
+}
+
+/// Conversion of the lexer, parser and interpreter errors into diagnostics
+/// worded in the chosen language.
+pub trait ToDiagnostic {
+    fn to_diagnostic(&self, lang: Language) -> Diagnostic;
+}
+
+impl ToDiagnostic for LexError {
+    fn to_diagnostic(&self, lang: Language) -> Diagnostic {
+        match self.kind {
+            LexErrorKind::UnexpectedCharacter => Diagnostic::error(
+                "E0101",
+                messages::format(lang, "E0101", &[("text", &self.text)]),
+                self.span,
+            )
+            .with_label(messages::text(lang, "E0101.label"))
+            .with_help(messages::text(lang, "E0101.help")),
+            LexErrorKind::UnterminatedString => {
+                Diagnostic::error("E0102", messages::text(lang, "E0102"), self.span)
+                    .with_label(messages::text(lang, "E0102.label"))
+                    .with_help(messages::text(lang, "E0102.help"))
+            }
+            LexErrorKind::InvalidEscape => Diagnostic::error(
+                "E0103",
+                messages::format(lang, "E0103", &[("text", &self.text)]),
+                self.span,
+            )
+            .with_label(messages::text(lang, "E0103.label"))
+            .with_help(messages::text(lang, "E0103.help")),
+            LexErrorKind::UnclosedInterpolation => {
+                Diagnostic::error("E0106", messages::text(lang, "E0106"), self.span)
+                    .with_label(messages::text(lang, "E0106.label"))
+                    .with_help(messages::text(lang, "E0106.help"))
+            }
+            LexErrorKind::NumberOutOfRange => Diagnostic::error(
+                "E0104",
+                messages::format(lang, "E0104", &[("text", &self.text)]),
+                self.span,
+            )
+            .with_label(messages::text(lang, "E0104.label"))
+            .with_note(messages::text(lang, "E0104.note")),
+            LexErrorKind::InvalidNumber => Diagnostic::error(
+                "E0105",
+                messages::format(lang, "E0105", &[("text", &self.text)]),
+                self.span,
+            )
+            .with_label(messages::text(lang, "E0105.label"))
+            .with_help(messages::text(lang, "E0105.help")),
+        }
+    }
+}
+
+fn describe_expected(expected: &Expected, lang: Language) -> String {
+    match expected {
+        Expected::Token(token) => format!("`{}`", token),
+        Expected::Identifier => messages::text(lang, "expected.name").to_string(),
+        Expected::Expression => messages::text(lang, "expected.value").to_string(),
+        Expected::Statement => messages::text(lang, "expected.statement").to_string(),
+        Expected::InterpolationEnd => "`}`".to_string(),
+    }
+}
+
+fn describe_found(found: &Token, lang: Language) -> String {
+    match found {
+        Token::TheEnd => messages::text(lang, "found.end").to_string(),
+        _ => format!("`{}`", found),
+    }
+}
+
+impl ToDiagnostic for ParseError {
+    fn to_diagnostic(&self, lang: Language) -> Diagnostic {
+        match self {
+            ParseError::UnexpectedToken {
+                expected: Expected::Statement,
+                found,
+                span,
+            } => Diagnostic::error(
+                "E0201",
+                messages::format(lang, "E0201", &[("found", found)]),
+                *span,
+            )
+            .with_label(messages::text(lang, "E0201.label"))
+            .with_help(messages::text(lang, "E0201.help")),
+            ParseError::UnexpectedToken {
+                expected,
+                found,
+                span,
+            } => {
+                let expected = describe_expected(expected, lang);
+                Diagnostic::error(
+                    "E0202",
+                    messages::format(
+                        lang,
+                        "E0202",
+                        &[
+                            ("expected", &expected),
+                            ("found", &describe_found(found, lang)),
+                        ],
+                    ),
+                    *span,
+                )
+                .with_label(messages::format(
+                    lang,
+                    "E0202.label",
+                    &[("expected", &expected)],
+                ))
+            }
+            ParseError::MissingBlockEnd { opened, span } => {
+                Diagnostic::error("E0203", messages::text(lang, "E0203"), *opened)
+                    .with_label(messages::text(lang, "E0203.label"))
+                    .with_secondary(*span, messages::text(lang, "file-ends-here"))
+                    .with_help(messages::text(lang, "E0203.help"))
+            }
+            ParseError::MissingParamEnd { function, span } => Diagnostic::error(
+                "E0204",
+                messages::format(lang, "E0204", &[("function", function)]),
+                *span,
+            )
+            .with_label(messages::text(lang, "E0204.label"))
+            .with_help(messages::text(lang, "E0204.help")),
+            ParseError::MissingOperator { count, span } => Diagnostic::error(
+                "E0206",
+                messages::format(lang, "E0206", &[("count", count)]),
+                *span,
+            )
+            .with_label(messages::text(lang, "E0206.label"))
+            .with_help(messages::text(lang, "E0206.help")),
+            ParseError::MissingOperand { op, span } => Diagnostic::error(
+                "E0207",
+                messages::format(lang, "E0207", &[("op", op)]),
+                *span,
+            )
+            .with_label(messages::text(lang, "E0207.label"))
+            .with_help(messages::format(lang, "E0207.help", &[("op", op)])),
+            ParseError::Redeclared {
+                name,
+                previous,
+                span,
+            } => Diagnostic::error(
+                "E0208",
+                messages::format(lang, "E0208", &[("name", name)]),
+                *span,
+            )
+            .with_label(messages::text(lang, "E0208.label"))
+            .with_secondary(*previous, messages::text(lang, "E0208.previous"))
+            .with_help(messages::format(lang, "E0208.help", &[("name", name)])),
+            ParseError::OutsideLoop { keyword, span } => Diagnostic::error(
+                "E0209",
+                messages::format(lang, "E0209", &[("keyword", keyword)]),
+                *span,
+            )
+            .with_label(messages::text(lang, "E0209.label"))
+            .with_help(messages::text(lang, "E0209.help")),
+            ParseError::ReturnOutsideFunction { span } => {
+                Diagnostic::error("E0210", messages::text(lang, "E0210"), *span)
+                    .with_label(messages::text(lang, "E0210.label"))
+            }
+            ParseError::InvalidAssignTarget { span } => {
+                Diagnostic::error("E0211", messages::text(lang, "E0211"), *span)
+                    .with_label(messages::text(lang, "E0211.label"))
+                    .with_help(messages::text(lang, "E0211.help"))
+            }
+            ParseError::DuplicateField {
+                name,
+                previous,
+                span,
+            } => Diagnostic::error(
+                "E0213",
+                messages::format(lang, "E0213", &[("name", name)]),
+                *span,
+            )
+            .with_label(messages::text(lang, "E0213.label"))
+            .with_secondary(*previous, messages::text(lang, "E0213.previous")),
+            ParseError::OddKoshItems { span } => {
+                Diagnostic::error("E0212", messages::text(lang, "E0212"), *span)
+                    .with_label(messages::text(lang, "E0212.label"))
+                    .with_help(messages::text(lang, "E0212.help"))
+            }
+            ParseError::ConstantAssignment {
+                name,
+                constant,
+                span,
+            } => Diagnostic::error(
+                "E0214",
+                messages::format(lang, "E0214", &[("name", name)]),
+                *span,
+            )
+            .with_label(messages::text(lang, "E0214.label"))
+            .with_secondary(*constant, messages::text(lang, "E0214.constant"))
+            .with_help(messages::format(lang, "E0214.help", &[("name", name)])),
+            ParseError::ConstantRedeclared {
+                name,
+                constant,
+                span,
+            } => Diagnostic::error(
+                "E0215",
+                messages::format(lang, "E0215", &[("name", name)]),
+                *span,
+            )
+            .with_label(messages::text(lang, "E0215.label"))
+            .with_secondary(*constant, messages::text(lang, "E0214.constant"))
+            .with_help(messages::text(lang, "E0215.help")),
+            ParseError::InvalidCase { span } => {
+                Diagnostic::error("E0216", messages::text(lang, "E0216"), *span)
+                    .with_label(messages::text(lang, "E0216.label"))
+                    .with_help(messages::text(lang, "E0216.help"))
+            }
+            ParseError::MissingCase { missing, span } => {
+                let args: &[(&str, &dyn std::fmt::Display)] = &[("value", missing)];
+                Diagnostic::error("E0217", messages::format(lang, "E0217", args), *span)
+                    .with_label(messages::format(lang, "E0217.label", args))
+                    .with_help(messages::format(lang, "E0217.help", args))
+            }
+        }
+    }
+}
+
+impl ToDiagnostic for ParseWarning {
+    fn to_diagnostic(&self, lang: Language) -> Diagnostic {
+        match self {
+            ParseWarning::UnreachableCase { previous, span } => {
+                let diagnostic = Diagnostic::warning("W0201", messages::text(lang, "W0201"), *span)
+                    .with_label(messages::text(lang, "W0201.label"));
+                match previous {
+                    Some(previous) => {
+                        diagnostic.with_secondary(*previous, messages::text(lang, "W0201.previous"))
+                    }
+                    None => diagnostic.with_note(messages::text(lang, "W0201.empty")),
+                }
+            }
+            ParseWarning::UnreachableDefault { span } => {
+                Diagnostic::warning("W0202", messages::text(lang, "W0202"), *span)
+                    .with_label(messages::text(lang, "W0202.label"))
+                    .with_note(messages::text(lang, "W0202.note"))
+            }
+        }
+    }
+}
+
+impl ToDiagnostic for RuntimeError {
+    fn to_diagnostic(&self, lang: Language) -> Diagnostic {
+        let span = self.span();
+        match self {
+            RuntimeError::TypeMismatch {
+                op, left, right, ..
+            } => {
+                let args: &[(&str, &dyn std::fmt::Display)] =
+                    &[("op", op), ("left", left), ("right", right)];
+                Diagnostic::error("E0301", messages::format(lang, "E0301", args), span)
+                    .with_note(messages::format(lang, "E0301.note", args))
+            }
+            RuntimeError::UndefinedVariable { name, .. } => Diagnostic::error(
+                "E0302",
+                messages::format(lang, "E0302", &[("name", name)]),
+                span,
+            )
+            .with_label(messages::text(lang, "E0302.label"))
+            .with_help(messages::format(lang, "E0302.help", &[("name", name)])),
+            RuntimeError::UndefinedFunction { name, .. } => Diagnostic::error(
+                "E0303",
+                messages::format(lang, "E0303", &[("name", name)]),
+                span,
+            )
+            .with_help(messages::format(lang, "E0303.help", &[("name", name)])),
+            RuntimeError::ArgumentCount {
+                name,
+                expected,
+                found,
+                ..
+            } => Diagnostic::error(
+                "E0304",
+                messages::format(lang, "E0304", &[("name", name)]),
+                span,
+            )
+            .with_label(messages::format(
+                lang,
+                "E0304.label",
+                &[("expected", expected), ("found", found)],
+            )),
+            RuntimeError::DivisionByZero { .. } => {
+                Diagnostic::error("E0305", messages::text(lang, "E0305"), span)
+                    .with_label(messages::text(lang, "E0305.label"))
+                    .with_help(messages::text(lang, "E0305.help"))
+            }
+            RuntimeError::Overflow { op, .. } => Diagnostic::error(
+                "E0306",
+                messages::format(lang, "E0306", &[("op", op)]),
+                span,
+            )
+            .with_note(messages::format(
+                lang,
+                "E0306.note",
+                &[("min", &i64::MIN), ("max", &i64::MAX)],
+            )),
+            RuntimeError::InputParse {
+                input, data_type, ..
+            } => Diagnostic::error(
+                "E0307",
+                messages::format(lang, "E0307", &[("input", input), ("data_type", data_type)]),
+                span,
+            )
+            .with_label(messages::format(
+                lang,
+                "E0307.label",
+                &[("data_type", data_type)],
+            )),
+            RuntimeError::UnknownInputType { data_type, .. } => Diagnostic::error(
+                "E0308",
+                messages::format(lang, "E0308", &[("data_type", data_type)]),
+                span,
+            )
+            .with_help(messages::text(lang, "E0308.help")),
+            RuntimeError::Io { message, .. } => Diagnostic::error(
+                "E0309",
+                messages::format(lang, "E0309", &[("message", message)]),
+                span,
+            ),
+            RuntimeError::UndeclaredAssignment { name, .. } => Diagnostic::error(
+                "E0310",
+                messages::format(lang, "E0310", &[("name", name)]),
+                span,
+            )
+            .with_help(messages::format(lang, "E0310.help", &[("name", name)])),
+            RuntimeError::LoopBound { found, .. } => Diagnostic::error(
+                "E0311",
+                messages::format(lang, "E0311", &[("found", found)]),
+                span,
+            )
+            .with_label(messages::format(lang, "E0311.label", &[("found", found)])),
+            RuntimeError::ZeroStep { .. } => {
+                Diagnostic::error("E0312", messages::text(lang, "E0312"), span)
+                    .with_label(messages::text(lang, "E0312.label"))
+                    .with_help(messages::text(lang, "E0312.help"))
+            }
+            RuntimeError::IndexOutOfRange { index, length, .. } => {
+                let args: &[(&str, &dyn std::fmt::Display)] =
+                    &[("index", index), ("length", length)];
+                let note = match length.checked_sub(1) {
+                    Some(last) => messages::format(lang, "E0313.note", &[("last", &last)]),
+                    None => messages::text(lang, "E0313.empty").to_string(),
+                };
+                Diagnostic::error("E0313", messages::format(lang, "E0313", args), span)
+                    .with_label(messages::text(lang, "E0313.label"))
+                    .with_note(note)
+            }
+            RuntimeError::WrongType {
+                expected, found, ..
+            } => {
+                let args: &[(&str, &dyn std::fmt::Display)] =
+                    &[("expected", expected), ("found", found)];
+                Diagnostic::error("E0314", messages::format(lang, "E0314", args), span)
+                    .with_label(messages::format(lang, "E0314.label", args))
+            }
+            RuntimeError::MissingKey { key, .. } => Diagnostic::error(
+                "E0315",
+                messages::format(lang, "E0315", &[("key", key)]),
+                span,
+            )
+            .with_help(messages::text(lang, "E0315.help")),
+            RuntimeError::InvalidKey { found, .. } => Diagnostic::error(
+                "E0316",
+                messages::format(lang, "E0316", &[("found", found)]),
+                span,
+            )
+            .with_label(messages::format(lang, "E0314.label", &[("found", found)])),
+            RuntimeError::UndefinedStruct { name, .. } => Diagnostic::error(
+                "E0317",
+                messages::format(lang, "E0317", &[("name", name)]),
+                span,
+            )
+            .with_help(messages::format(lang, "E0317.help", &[("name", name)])),
+            RuntimeError::FieldCount {
+                name,
+                expected,
+                found,
+                ..
+            } => {
+                let args: &[(&str, &dyn std::fmt::Display)] =
+                    &[("name", name), ("expected", expected), ("found", found)];
+                Diagnostic::error("E0318", messages::format(lang, "E0318", args), span)
+                    .with_label(messages::format(lang, "E0318.label", args))
+            }
+            RuntimeError::UnknownField { record, field, .. } => {
+                let args: &[(&str, &dyn std::fmt::Display)] =
+                    &[("record", record), ("field", field)];
+                Diagnostic::error("E0319", messages::format(lang, "E0319", args), span)
+            }
+            RuntimeError::Thrown { value, .. } => Diagnostic::error(
+                "E0320",
+                messages::format(lang, "E0320", &[("value", value)]),
+                span,
+            )
+            .with_label(messages::text(lang, "E0320.label"))
+            .with_help(messages::text(lang, "E0320.help")),
+            RuntimeError::CallDepth { name, .. } => {
+                let args: &[(&str, &dyn std::fmt::Display)] =
+                    &[("name", name), ("limit", &MAX_CALL_DEPTH)];
+                Diagnostic::error("E0321", messages::format(lang, "E0321", args), span)
+                    .with_help(messages::text(lang, "E0321.help"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "mano x barabar 1\n\tmano x barabar 2\n";
+
+    fn redeclared() -> Diagnostic {
+        let first = Span {
+            start: 5,
+            end: 6,
+            line: 1,
+            column: 6,
+        };
+        let second = Span {
+            start: 23,
+            end: 24,
+            line: 2,
+            column: 7,
+        };
+        Diagnostic::error("E0208", "`x` is declared twice", second)
+            .with_label("declared again here")
+            .with_secondary(first, "first declared here")
+            .with_note("blocks have their own names")
+            .with_help("use `badlo`")
+    }
+
+    #[test]
+    fn plain_render_underlines_every_label_and_keeps_tabs() {
+        let rendered = redeclared().render(SOURCE, "main.bhasha", false, Language::English);
+        assert_eq!(
+            rendered,
+            "error[E0208]: `x` is declared twice\n\
+             \x20--> main.bhasha:2:7\n\
+             \x20 |\n\
+             1 | mano x barabar 1\n\
+             \x20 |      - first declared here\n\
+             2 | \tmano x barabar 2\n\
+             \x20 | \t     ^ declared again here\n\
+             \x20 |\n\
+             \x20 = note: blocks have their own names\n\
+             \x20 = help: use `badlo`\n"
+        );
+    }
+
+    #[test]
+    fn coloured_render_paints_each_part() {
+        let rendered = redeclared().render(SOURCE, "main.bhasha", true, Language::English);
+        assert!(rendered
+            .starts_with("\x1b[1;31merror[E0208]\x1b[0m\x1b[1m: `x` is declared twice\x1b[0m\n"));
+        assert!(rendered.contains("\x1b[1;34m- first declared here\x1b[0m"));
+        assert!(rendered.contains("\x1b[1;31m^ declared again here\x1b[0m"));
+        assert!(rendered.contains("\x1b[1;34m=\x1b[0m \x1b[1mhelp\x1b[0m: use `badlo`"));
+
+        let warning = Diagnostic::warning("W0201", "unreachable", redeclared().span);
+        let rendered = warning.render(SOURCE, "main.bhasha", true, Language::English);
+        assert!(rendered.starts_with("\x1b[1;33mwarning[W0201]\x1b[0m"));
+    }
+}
@@ -0,0 +1,263 @@
+// src/dialect.rs
+
+use crate::span::Span;
+use crate::tokens::{TemplatePart, Token};
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// A keyword table that lets a program be written in another language.
+///
+/// A dialect file has one `phrase = keyword` mapping per line, where the
+/// right-hand side is any built-in keyword or operator phrase:
+///
+/// ```text
+/// faltu Marathi
+/// jar = agar
+/// chi beriz = ka yog
+/// peksha motha aahe = se bada hai
+/// ```
+///
+/// Blank lines and `faltu` comments are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Dialect {
+    /// Phrases split into words, longest first so `nahi aahe` wins over `nahi`.
+    phrases: Vec<(Vec<String>, Token)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialectError {
+    Io {
+        path: String,
+        message: String,
+    },
+    /// A line is not of the form `phrase = keyword`.
+    Syntax {
+        line: usize,
+    },
+    /// The right-hand side of a mapping is not a single keyword.
+    NotKeyword {
+        line: usize,
+        text: String,
+    },
+}
+
+impl fmt::Display for DialectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DialectError::Io { path, message } => write!(f, "{}: {}", path, message),
+            DialectError::Syntax { line } => {
+                write!(f, "line {}: expected `phrase = keyword`", line)
+            }
+            DialectError::NotKeyword { line, text } => {
+                write!(f, "line {}: `{}` is not a keyword", line, text)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DialectError {}
+
+impl Dialect {
+    pub fn parse(text: &str) -> Result<Dialect, DialectError> {
+        let mut phrases = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("faltu") {
+                continue;
+            }
+
+            let (phrase, keyword) = line
+                .split_once('=')
+                .ok_or(DialectError::Syntax { line: line_number })?;
+            let words: Vec<String> = phrase.split_whitespace().map(str::to_string).collect();
+            if words.is_empty() {
+                return Err(DialectError::Syntax { line: line_number });
+            }
+
+            let keyword = keyword.trim();
+            let not_keyword = || DialectError::NotKeyword {
+                line: line_number,
+                text: keyword.to_string(),
+            };
+            let token = match Token::tokenize(keyword).as_deref() {
+                Ok([(token, _), (Token::TheEnd, _)]) if token.is_keyword() => token.clone(),
+                _ => return Err(not_keyword()),
+            };
+
+            phrases.push((words, token));
+        }
+
+        phrases.sort_by_key(|(words, _)| std::cmp::Reverse(words.len()));
+        Ok(Dialect { phrases })
+    }
+
+    pub fn load(path: &Path) -> Result<Dialect, DialectError> {
+        let text = fs::read_to_string(path).map_err(|error| DialectError::Io {
+            path: path.display().to_string(),
+            message: error.to_string(),
+        })?;
+        Dialect::parse(&text)
+    }
+
+    /// Finds a `faltu boli: <file>` directive on the leading comment lines of
+    /// `source`, naming the dialect the file is written in.
+    pub fn directive(source: &str) -> Option<&str> {
+        source
+            .lines()
+            .map(str::trim)
+            .take_while(|line| line.is_empty() || line.starts_with("faltu"))
+            .find_map(|line| line.strip_prefix("faltu boli:"))
+            .map(str::trim)
+    }
+
+    /// Rewrites every run of tokens whose source text spells a dialect
+    /// phrase into the keyword it stands for.
+    pub fn apply(&self, source: &str, tokens: Vec<(Token, Span)>) -> Vec<(Token, Span)> {
+        if self.phrases.is_empty() {
+            return tokens;
+        }
+
+        let mut result = Vec::with_capacity(tokens.len());
+        let mut index = 0;
+        while index < tokens.len() {
+            match self.match_at(source, &tokens[index..]) {
+                Some((token, consumed)) => {
+                    let span = tokens[index].1.to(tokens[index + consumed - 1].1);
+                    result.push((token, span));
+                    index += consumed;
+                }
+                None => {
+                    let (token, span) = &tokens[index];
+                    let token = match token {
+                        Token::Template(parts) => Token::Template(
+                            parts
+                                .iter()
+                                .map(|part| match part {
+                                    TemplatePart::Code(code) => {
+                                        TemplatePart::Code(self.apply(source, code.clone()))
+                                    }
+                                    text => text.clone(),
+                                })
+                                .collect(),
+                        ),
+                        token => token.clone(),
+                    };
+                    result.push((token, *span));
+                    index += 1;
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns the keyword for the longest phrase starting at the first of
+    /// `tokens`, and how many tokens it covers.
+    fn match_at(&self, source: &str, tokens: &[(Token, Span)]) -> Option<(Token, usize)> {
+        'phrases: for (words, token) in &self.phrases {
+            let mut word = 0;
+            for (consumed, (_, span)) in tokens.iter().enumerate() {
+                for part in source[span.range()].split_whitespace() {
+                    if words.get(word).map(String::as_str) != Some(part) {
+                        continue 'phrases;
+                    }
+                    word += 1;
+                }
+                if word == words.len() {
+                    return Some((token.clone(), consumed + 1));
+                }
+                if word == 0 {
+                    continue 'phrases;
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(dialect: &Dialect, source: &str) -> Vec<Token> {
+        let tokens = Token::tokenize(source).unwrap();
+        dialect
+            .apply(source, tokens)
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect()
+    }
+
+    #[test]
+    fn parse_maps_phrases_to_keywords() {
+        let dialect = Dialect::parse("faltu Marathi\n\njar = agar\nchi beriz = ka yog\n").unwrap();
+        assert_eq!(
+            apply(&dialect, "jar 1 2 chi beriz"),
+            [
+                Token::If,
+                Token::Integer(1),
+                Token::Integer(2),
+                Token::Plus,
+                Token::TheEnd
+            ]
+        );
+    }
+
+    #[test]
+    fn longest_phrase_wins() {
+        let dialect = Dialect::parse("nahi = nhi\nnahi aahe = nhi hai").unwrap();
+        assert_eq!(
+            apply(&dialect, "nahi aahe"),
+            [Token::NotEquals, Token::TheEnd]
+        );
+        assert_eq!(
+            apply(&dialect, "nahi x"),
+            [Token::Not, Token::Identifier("x".into()), Token::TheEnd]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_lines_without_a_mapping() {
+        assert_eq!(
+            Dialect::parse("jar = agar\njar agar").unwrap_err(),
+            DialectError::Syntax { line: 2 }
+        );
+        assert_eq!(
+            Dialect::parse(" = agar").unwrap_err(),
+            DialectError::Syntax { line: 1 }
+        );
+    }
+
+    #[test]
+    fn parse_rejects_targets_that_are_not_one_keyword() {
+        assert_eq!(
+            Dialect::parse("x = naam").unwrap_err(),
+            DialectError::NotKeyword {
+                line: 1,
+                text: "naam".to_string()
+            }
+        );
+        assert_eq!(
+            Dialect::parse("x = agar warna").unwrap_err(),
+            DialectError::NotKeyword {
+                line: 1,
+                text: "agar warna".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn directive_is_read_from_leading_comments_only() {
+        assert_eq!(
+            Dialect::directive("faltu boli: marathi.boli\nlikho 1"),
+            Some("marathi.boli")
+        );
+        assert_eq!(
+            Dialect::directive("likho 1\nfaltu boli: marathi.boli"),
+            None
+        );
+    }
+}
@@ -1,8 +1,15 @@
-use crate::ast::{BinOp, Expression, Program, Statement};
+use crate::ast::{
+    BinOp, Case, Expression, ExpressionKind, Program, Statement, StatementKind, StringPart,
+};
+use crate::diagnostics::ToDiagnostic;
+use crate::messages::Language;
+use crate::span::Span;
 
-use std::collections::HashMap;
-use std::io::{self, Write};
-use std::process::exit;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::rc::Rc;
 
 #[derive(Debug, Clone)]
 pub enum Value {
@@ -10,235 +17,1238 @@ pub enum Value {
     Float(f64),
     String(String),
     Boolean(bool),
+    /// A list, shared between every variable that holds it, so changes
+    /// made through one name are seen through the others.
+    List(Rc<RefCell<Vec<Value>>>),
+    /// A kosh (dictionary), shared like a list and kept sorted by key.
+    Kosh(Rc<RefCell<BTreeMap<Key, Value>>>),
+    /// An instance of a `dhancha`, shared like a list.
+    Record(Rc<RefCell<Record>>),
+    /// A function, made by `banao`, that can be stored and passed around
+    /// like any other value.
+    Function(Rc<Function>),
     None,
 }
 
+/// A function value together with the scope it was made in, so its body
+/// can still see the variables that were around at that point.
+pub struct Function {
+    /// The name given in `banao`, or `None` for an anonymous function.
+    pub name: Option<String>,
+    pub params: Vec<String>,
+    body: Vec<Statement>,
+    closure: Env,
+}
+
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The closure is left out: it usually holds the function itself.
+        f.debug_struct("Function")
+            .field("name", &self.name)
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+type Env = Rc<RefCell<Scope>>;
+
+/// The variables of one scope, and the scope it is nested in.
+#[derive(Default)]
+struct Scope {
+    vars: HashMap<String, Value>,
+    parent: Option<Env>,
+}
+
+impl Scope {
+    fn child(parent: &Env) -> Env {
+        Rc::new(RefCell::new(Scope {
+            vars: HashMap::new(),
+            parent: Some(Rc::clone(parent)),
+        }))
+    }
+
+    /// Finds `name` in this scope or the nearest enclosing one.
+    fn get(env: &Env, name: &str) -> Option<Value> {
+        let scope = env.borrow();
+        match scope.vars.get(name) {
+            Some(value) => Some(value.clone()),
+            None => scope
+                .parent
+                .as_ref()
+                .and_then(|parent| Scope::get(parent, name)),
+        }
+    }
+
+    /// Overwrites `name` in the nearest scope that has it, returning
+    /// `false` when no scope does.
+    fn set(env: &Env, name: &str, value: Value) -> bool {
+        let mut scope = env.borrow_mut();
+        if let Some(slot) = scope.vars.get_mut(name) {
+            *slot = value;
+            return true;
+        }
+        match &scope.parent {
+            Some(parent) => Scope::set(parent, name, value),
+            None => false,
+        }
+    }
+}
+
+/// The type name and field values of a `dhancha` instance, in the order
+/// the fields were declared.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub name: String,
+    pub fields: Vec<(String, Value)>,
+}
+
+/// Writes a value inside a suchi, kosh or dhancha, quoting text so that
+/// `["1"]` and `[1]` print differently.
+fn write_nested(
+    f: &mut fmt::Formatter,
+    value: &Value,
+    printing: &mut Vec<*const ()>,
+) -> fmt::Result {
+    match value {
+        Value::String(x) => write_quoted(f, x),
+        value => write_value(f, value, printing),
+    }
+}
+
+/// Writes `text` in double quotes, escaped the way it would be written in
+/// a program. Unlike `{:?}`, this leaves Devanagari vowel signs alone.
+fn write_quoted(f: &mut fmt::Formatter, text: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '{' => write!(f, "\\{{")?,
+            '}' => write!(f, "\\}}")?,
+            '\n' => write!(f, "\\n")?,
+            '\t' => write!(f, "\\t")?,
+            '\r' => write!(f, "\\r")?,
+            '\0' => write!(f, "\\0")?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// A kosh key: only numbers and text can be keys.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Key {
+    Integer(i64),
+    String(String),
+}
+
+impl Key {
+    fn from_value(value: &Value) -> Option<Key> {
+        match value {
+            Value::Integer(i) => Some(Key::Integer(*i)),
+            Value::String(s) => Some(Key::String(s.clone())),
+            _ => None,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            Key::Integer(i) => Value::Integer(*i),
+            Key::String(s) => Value::String(s.clone()),
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Key::Integer(i) => write!(f, "{}", i),
+            Key::String(s) => write_quoted(f, s),
+        }
+    }
+}
+
+impl Value {
+    /// The name of this value's type, as spelled in `padho`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Integer(_) => "sankhya",
+            Value::Float(_) => "dasamlav",
+            Value::String(_) => "paath",
+            Value::Boolean(_) => "tark",
+            Value::List(_) => "suchi",
+            Value::Kosh(_) => "kosh",
+            Value::Record(_) => "dhancha",
+            Value::Function(_) => "kaam",
+            Value::None => "khali",
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        values_equal(self, other)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_value(f, self, &mut Vec::new())
+    }
+}
+
+/// Writes `value`, where `printing` holds the collections it is nested in.
+/// A collection that contains itself is written as `[...]` the second time
+/// instead of forever.
+fn write_value(
+    f: &mut fmt::Formatter,
+    value: &Value,
+    printing: &mut Vec<*const ()>,
+) -> fmt::Result {
+    let address = match value {
+        Value::List(items) => Some(Rc::as_ptr(items) as *const ()),
+        Value::Kosh(entries) => Some(Rc::as_ptr(entries) as *const ()),
+        Value::Record(record) => Some(Rc::as_ptr(record) as *const ()),
+        _ => None,
+    };
+    if let Some(address) = address {
+        if printing.contains(&address) {
+            return write!(f, "[...]");
+        }
+        printing.push(address);
+    }
+    let result = match value {
+        Value::String(x) => write!(f, "{}", x),
+        Value::Boolean(x) => write!(f, "{}", x),
+        Value::Float(x) => write!(f, "{}", x),
+        Value::Integer(x) => write!(f, "{}", x),
+        Value::List(items) => {
+            write!(f, "[")?;
+            for (i, item) in items.borrow().iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write_nested(f, item, printing)?;
+            }
+            write!(f, "]")
+        }
+        Value::Kosh(entries) => {
+            write!(f, "{{")?;
+            for (i, (key, value)) in entries.borrow().iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}: ", key)?;
+                write_nested(f, value, printing)?;
+            }
+            write!(f, "}}")
+        }
+        Value::Record(record) => {
+            let record = record.borrow();
+            write!(f, "{} {{", record.name)?;
+            for (i, (field, value)) in record.fields.iter().enumerate() {
+                write!(f, "{}{}: ", if i > 0 { ", " } else { " " }, field)?;
+                write_nested(f, value, printing)?;
+            }
+            write!(f, " }}")
+        }
+        Value::Function(function) => match &function.name {
+            Some(name) => write!(f, "<kaam {}>", name),
+            None => write!(f, "<kaam>"),
+        },
+        Value::None => write!(f, "khali"),
+    };
+    if address.is_some() {
+        printing.pop();
+    }
+    result
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuntimeError {
+    TypeMismatch {
+        op: BinOp,
+        left: &'static str,
+        right: &'static str,
+        span: Span,
+    },
+    UndefinedVariable {
+        name: String,
+        span: Span,
+    },
+    UndefinedFunction {
+        name: String,
+        span: Span,
+    },
+    ArgumentCount {
+        name: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    DivisionByZero {
+        span: Span,
+    },
+    Overflow {
+        op: BinOp,
+        span: Span,
+    },
+    /// The text typed at `padho` is not a valid `data_type`.
+    InputParse {
+        input: String,
+        data_type: String,
+        span: Span,
+    },
+    UnknownInputType {
+        data_type: String,
+        span: Span,
+    },
+    Io {
+        message: String,
+        span: Span,
+    },
+    /// `badlo` of a name that was never declared with `mano`.
+    UndeclaredAssignment {
+        name: String,
+        span: Span,
+    },
+    /// A bound or step of a `har` loop is not a sankhya.
+    LoopBound {
+        found: &'static str,
+        span: Span,
+    },
+    /// A `har` loop with `kadam 0`, which would never finish.
+    ZeroStep {
+        span: Span,
+    },
+    /// A list index below zero or past the last element.
+    IndexOutOfRange {
+        index: i64,
+        length: usize,
+        span: Span,
+    },
+    /// A value of the wrong type where only `expected` makes sense.
+    WrongType {
+        expected: &'static str,
+        found: &'static str,
+        span: Span,
+    },
+    /// A kosh lookup or removal of a key it does not have.
+    MissingKey {
+        key: String,
+        span: Span,
+    },
+    /// A kosh key that is neither a sankhya nor a paath.
+    InvalidKey {
+        found: &'static str,
+        span: Span,
+    },
+    UndefinedStruct {
+        name: String,
+        span: Span,
+    },
+    /// `naya` given a different number of values than the dhancha has fields.
+    FieldCount {
+        name: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    UnknownField {
+        record: String,
+        field: String,
+        span: Span,
+    },
+    /// A `fenko` that no `koshish` caught.
+    Thrown {
+        value: Value,
+        span: Span,
+    },
+    /// A call to `name` made while [`MAX_CALL_DEPTH`] calls were already
+    /// running, usually a recursion that never stops.
+    CallDepth {
+        name: String,
+        span: Span,
+    },
+}
+
+impl RuntimeError {
+    pub fn span(&self) -> Span {
+        match self {
+            RuntimeError::TypeMismatch { span, .. }
+            | RuntimeError::UndefinedVariable { span, .. }
+            | RuntimeError::UndefinedFunction { span, .. }
+            | RuntimeError::ArgumentCount { span, .. }
+            | RuntimeError::DivisionByZero { span }
+            | RuntimeError::Overflow { span, .. }
+            | RuntimeError::InputParse { span, .. }
+            | RuntimeError::UnknownInputType { span, .. }
+            | RuntimeError::Io { span, .. }
+            | RuntimeError::UndeclaredAssignment { span, .. }
+            | RuntimeError::LoopBound { span, .. }
+            | RuntimeError::ZeroStep { span }
+            | RuntimeError::IndexOutOfRange { span, .. }
+            | RuntimeError::WrongType { span, .. }
+            | RuntimeError::MissingKey { span, .. }
+            | RuntimeError::InvalidKey { span, .. }
+            | RuntimeError::UndefinedStruct { span, .. }
+            | RuntimeError::FieldCount { span, .. }
+            | RuntimeError::UnknownField { span, .. }
+            | RuntimeError::Thrown { span, .. }
+            | RuntimeError::CallDepth { span, .. } => *span,
+        }
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let span = self.span();
+        write!(f, "{}:{}: ", span.line, span.column)?;
+        match self {
+            RuntimeError::TypeMismatch {
+                op, left, right, ..
+            } => write!(f, "cannot use `{}` with {} and {}", op, left, right),
+            RuntimeError::UndefinedVariable { name, .. } => {
+                write!(f, "variable `{}` is not defined", name)
+            }
+            RuntimeError::UndefinedFunction { name, .. } => {
+                write!(f, "function `{}` is not defined", name)
+            }
+            RuntimeError::ArgumentCount {
+                name,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "function `{}` takes {} arguments but {} were given",
+                name, expected, found
+            ),
+            RuntimeError::DivisionByZero { .. } => write!(f, "division by zero"),
+            RuntimeError::Overflow { op, .. } => {
+                write!(f, "result of `{}` is too large for a sankhya", op)
+            }
+            RuntimeError::InputParse {
+                input, data_type, ..
+            } => write!(f, "`{}` is not a valid {}", input, data_type),
+            RuntimeError::UnknownInputType { data_type, .. } => {
+                write!(f, "unknown input type `{}`", data_type)
+            }
+            RuntimeError::Io { message, .. } => write!(f, "{}", message),
+            RuntimeError::UndeclaredAssignment { name, .. } => {
+                write!(f, "cannot change `{}`, which was never declared", name)
+            }
+            RuntimeError::LoopBound { found, .. } => {
+                write!(f, "loop bounds must be sankhya, found {}", found)
+            }
+            RuntimeError::ZeroStep { .. } => write!(f, "loop step cannot be zero"),
+            RuntimeError::IndexOutOfRange { index, length, .. } => write!(
+                f,
+                "index {} is out of range for a suchi of length {}",
+                index, length
+            ),
+            RuntimeError::WrongType {
+                expected, found, ..
+            } => write!(f, "expected {}, found {}", expected, found),
+            RuntimeError::MissingKey { key, .. } => write!(f, "key {} is not in the kosh", key),
+            RuntimeError::InvalidKey { found, .. } => {
+                write!(f, "a kosh key must be a sankhya or paath, found {}", found)
+            }
+            RuntimeError::UndefinedStruct { name, .. } => {
+                write!(f, "dhancha `{}` is not defined", name)
+            }
+            RuntimeError::FieldCount {
+                name,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "dhancha `{}` has {} fields but {} values were given",
+                name, expected, found
+            ),
+            RuntimeError::UnknownField { record, field, .. } => {
+                write!(f, "dhancha `{}` has no field `{}`", record, field)
+            }
+            RuntimeError::Thrown { value, .. } => write!(f, "uncaught `fenko`: {}", value),
+            RuntimeError::CallDepth { name, .. } => write!(
+                f,
+                "calling `{}` would nest more than {} calls",
+                name, MAX_CALL_DEPTH
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+
+/// How a statement finished: normally, by leaving the enclosing loop, or
+/// by returning from the enclosing function.
+#[derive(Debug, Clone)]
+enum Flow {
+    Normal,
+    Break,
+    Continue,
+    Return(Value),
+}
+
+/// How deeply function calls may nest before [`RuntimeError::CallDepth`]
+/// stops them.
+pub const MAX_CALL_DEPTH: usize = 1000;
+
+/// A call that finds less than this much native stack left runs on a new
+/// [`STACK_GROWTH`] segment, so `MAX_CALL_DEPTH` holds on any thread.
+const STACK_RED_ZONE: usize = 256 * 1024;
+const STACK_GROWTH: usize = 4 * 1024 * 1024;
+
+#[derive(Default)]
 pub struct Interpreter {
-    env: HashMap<String, Value>,
-    functions: HashMap<String, (Vec<String>, Vec<Statement>, Expression)>,
+    /// The innermost scope; the scopes around it are reached through
+    /// its parents.
+    env: Env,
+    /// Field names of each `dhancha`.
+    structs: HashMap<String, Vec<String>>,
+    /// The language of the messages a `pakdo` sees.
+    lang: Language,
+    /// How many function calls are running.
+    depth: usize,
+    /// Where `padho` reads from instead of standard input.
+    input: Option<Box<dyn BufRead>>,
 }
 
 impl Interpreter {
     pub fn new() -> Self {
         Interpreter {
-            env: HashMap::new(),
-            functions: HashMap::new()
-        }
-    }
-
-    fn eval_expr(&mut self, expr: &Expression) -> Value {
-        match expr {
-            Expression::Integer(i) => Value::Integer(*i),
-            Expression::Float(f) => Value::Float(*f),
-            Expression::String(s) => Value::String(s.clone()),
-            Expression::Boolean(b) => Value::Boolean(*b),
-            Expression::Identifier(name) => self.env.get(name).cloned().unwrap_or(Value::None),
-            Expression::BinaryOp(lhs, op, rhs) => {
-                let left = self.eval_expr(lhs);
-                let right = self.eval_expr(rhs);
-                match (left, right, op) {
-                    (Value::Integer(l), Value::Integer(r), BinOp::Plus) => Value::Integer(l + r),
-                    (Value::Float(l), Value::Float(r), BinOp::Plus) => Value::Float(l + r),
-                    (Value::String(l), Value::String(r), BinOp::Plus) => {
-                        Value::String(format!("{}{}", l, r))
-                    }
-                    (Value::String(l), _, BinOp::Plus) => {
-                        Value::String(format!("{}{}", l, stringify!(r)))
-                    }
-                    (_, Value::String(r), BinOp::Plus) => {
-                        Value::String(format!("{}{}", stringify!(l), r))
-                    }
-                    (Value::Integer(l), Value::Float(r), BinOp::Plus) => Value::Float(l as f64 + r),
-                    (Value::Float(l), Value::Integer(r), BinOp::Plus) => Value::Float(l + r as f64),
-                    (Value::Integer(l), Value::Integer(r), BinOp::Minus) => Value::Integer(l - r),
-                    (Value::Float(l), Value::Float(r), BinOp::Minus) => Value::Float(l - r),
-                    (Value::Integer(l), Value::Integer(r), BinOp::Multiply) => {
-                        Value::Integer(l * r)
-                    }
-                    (Value::Float(l), Value::Float(r), BinOp::Multiply) => Value::Float(l * r),
-                    (Value::Integer(l), Value::Integer(r), BinOp::Divide) => Value::Integer(l / r),
-                    (Value::Float(l), Value::Float(r), BinOp::Divide) => Value::Float(l / r),
-                    (Value::Integer(l), Value::Integer(r), BinOp::Modulo) => Value::Integer(l % r),
-                    (Value::Float(l), Value::Float(r), BinOp::Modulo) => Value::Float(l % r),
-                    (Value::Integer(l), Value::Integer(r), BinOp::LessThan) => {
-                        Value::Boolean(l < r)
-                    }
-                    (Value::Float(l), Value::Float(r), BinOp::LessThan) => Value::Boolean(l < r),
-                    (Value::Integer(l), Value::Integer(r), BinOp::GreaterThan) => {
-                        Value::Boolean(l > r)
-                    }
-                    (Value::Float(l), Value::Float(r), BinOp::GreaterThan) => Value::Boolean(l > r),
-                    (Value::Integer(l), Value::Integer(r), BinOp::LessThanOrEqual) => {
-                        Value::Boolean(l <= r)
-                    }
-                    (Value::Float(l), Value::Float(r), BinOp::LessThanOrEqual) => {
-                        Value::Boolean(l <= r)
-                    }
-                    (Value::Integer(l), Value::Integer(r), BinOp::GreaterThanOrEqual) => {
-                        Value::Boolean(l >= r)
-                    }
-                    (Value::Float(l), Value::Float(r), BinOp::GreaterThanOrEqual) => {
-                        Value::Boolean(l >= r)
-                    }
-                    (Value::Integer(l), Value::Integer(r), BinOp::Equals) => Value::Boolean(l == r),
-                    (Value::Float(l), Value::Float(r), BinOp::Equals) => Value::Boolean(l == r),
-                    (Value::String(l), Value::String(r), BinOp::Equals) => Value::Boolean(l == r),
-                    (Value::Boolean(l), Value::Boolean(r), BinOp::Equals) => Value::Boolean(l == r),
-                    (Value::Integer(l), Value::Integer(r), BinOp::NotEquals) => {
-                        Value::Boolean(l != r)
+            env: Env::default(),
+            structs: HashMap::new(),
+            lang: Language::default(),
+            depth: 0,
+            input: None,
+        }
+    }
+
+    pub fn with_language(mut self, lang: Language) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// Makes `padho` read its lines from `input` rather than standard input.
+    pub fn with_input(mut self, input: impl BufRead + 'static) -> Self {
+        self.input = Some(Box::new(input));
+        self
+    }
+
+    fn eval_expr(&mut self, expr: &Expression) -> Result<Value, RuntimeError> {
+        match &expr.kind {
+            ExpressionKind::Integer(i) => Ok(Value::Integer(*i)),
+            ExpressionKind::Float(f) => Ok(Value::Float(*f)),
+            ExpressionKind::String(s) => Ok(Value::String(s.clone())),
+            ExpressionKind::Boolean(b) => Ok(Value::Boolean(*b)),
+            ExpressionKind::Identifier(name) => {
+                Scope::get(&self.env, name).ok_or_else(|| RuntimeError::UndefinedVariable {
+                    name: name.clone(),
+                    span: expr.span,
+                })
+            }
+            // `aur` and `ya` only evaluate their right side when the left
+            // side does not already decide the result.
+            ExpressionKind::BinaryOp(lhs, op @ (BinOp::And | BinOp::Or), rhs) => {
+                let left = self.eval_expr(lhs)?;
+                let left = self.is_truthy(&left);
+                if left == (*op == BinOp::Or) {
+                    return Ok(Value::Boolean(left));
+                }
+                let right = self.eval_expr(rhs)?;
+                Ok(Value::Boolean(self.is_truthy(&right)))
+            }
+            ExpressionKind::BinaryOp(lhs, op, rhs) => {
+                let left = self.eval_expr(lhs)?;
+                let right = self.eval_expr(rhs)?;
+                self.eval_binary(left, op, right, expr.span)
+            }
+            ExpressionKind::Call(func_name, args) => self.call_function(func_name, args, expr.span),
+            ExpressionKind::Function(params, body) => Ok(self.make_function(None, params, body)),
+            ExpressionKind::List(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| self.eval_expr(item))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::List(Rc::new(RefCell::new(items))))
+            }
+            ExpressionKind::Kosh(items) => {
+                let mut entries = BTreeMap::new();
+                for (key, value) in items {
+                    let key = self.eval_key(key)?;
+                    entries.insert(key, self.eval_expr(value)?);
+                }
+                Ok(Value::Kosh(Rc::new(RefCell::new(entries))))
+            }
+            ExpressionKind::Record(name, values) => {
+                let fields = self.structs.get(name).cloned().ok_or_else(|| {
+                    RuntimeError::UndefinedStruct {
+                        name: name.clone(),
+                        span: expr.span,
                     }
-                    (Value::Float(l), Value::Float(r), BinOp::NotEquals) => Value::Boolean(l != r),
-                    (Value::String(l), Value::String(r), BinOp::NotEquals) => {
-                        Value::Boolean(l != r)
+                })?;
+
+                if fields.len() != values.len() {
+                    return Err(RuntimeError::FieldCount {
+                        name: name.clone(),
+                        expected: fields.len(),
+                        found: values.len(),
+                        span: expr.span,
+                    });
+                }
+
+                let mut record = Record {
+                    name: name.clone(),
+                    fields: Vec::with_capacity(fields.len()),
+                };
+                for (field, value) in fields.into_iter().zip(values) {
+                    record.fields.push((field, self.eval_expr(value)?));
+                }
+                Ok(Value::Record(Rc::new(RefCell::new(record))))
+            }
+            ExpressionKind::Field(record, field) => {
+                let record = self.eval_record(record)?;
+                let record = record.borrow();
+                record
+                    .fields
+                    .iter()
+                    .find(|(name, _)| name == field)
+                    .map(|(_, value)| value.clone())
+                    .ok_or_else(|| RuntimeError::UnknownField {
+                        record: record.name.clone(),
+                        field: field.clone(),
+                        span: expr.span,
+                    })
+            }
+            ExpressionKind::Length(value) => {
+                let length = match self.eval_expr(value)? {
+                    Value::List(items) => items.borrow().len(),
+                    Value::Kosh(entries) => entries.borrow().len(),
+                    Value::String(text) => text.chars().count(),
+                    other => {
+                        return Err(RuntimeError::WrongType {
+                            expected: "suchi",
+                            found: other.type_name(),
+                            span: value.span,
+                        })
                     }
-                    (Value::Boolean(l), Value::Boolean(r), BinOp::NotEquals) => {
-                        Value::Boolean(l != r)
+                };
+                Ok(Value::Integer(length as i64))
+            }
+            ExpressionKind::Not(operand) => {
+                let value = self.eval_expr(operand)?;
+                Ok(Value::Boolean(!self.is_truthy(&value)))
+            }
+            ExpressionKind::Interpolated(parts) => {
+                let mut text = String::new();
+                for part in parts {
+                    match part {
+                        StringPart::Text(part) => text.push_str(part),
+                        StringPart::Expression(part) => {
+                            text.push_str(&self.eval_expr(part)?.to_string())
+                        }
                     }
-                    (Value::Boolean(l), Value::Boolean(r), BinOp::And) => Value::Boolean(l && r),
-                    (Value::Boolean(l), Value::Boolean(r), BinOp::Or) => Value::Boolean(l || r),
-                    _ => panic!("Invalid operation"),
                 }
+                Ok(Value::String(text))
             }
-            Expression::None => panic!("Invalid Token"),
         }
     }
 
-    fn exec_stmt(&mut self, stmt: &Statement) {
-        match stmt {
-            Statement::Declaration(name, expr) => {
-                let value = self.eval_expr(expr);
-                self.env.insert(name.clone(), value);
+    fn eval_binary(
+        &self,
+        left: Value,
+        op: &BinOp,
+        right: Value,
+        span: Span,
+    ) -> Result<Value, RuntimeError> {
+        let overflow = || RuntimeError::Overflow {
+            op: op.clone(),
+            span,
+        };
+        let value = match (&left, &right, op) {
+            (Value::Integer(l), Value::Integer(r), BinOp::Plus) => {
+                Value::Integer(l.checked_add(*r).ok_or_else(overflow)?)
+            }
+            (Value::Float(l), Value::Float(r), BinOp::Plus) => Value::Float(l + r),
+            (Value::String(l), Value::String(r), BinOp::Plus) => {
+                Value::String(format!("{}{}", l, r))
+            }
+            (Value::String(l), r, BinOp::Plus) => Value::String(format!("{}{}", l, r)),
+            (l, Value::String(r), BinOp::Plus) => Value::String(format!("{}{}", l, r)),
+            (Value::Integer(l), Value::Float(r), BinOp::Plus) => Value::Float(*l as f64 + r),
+            (Value::Float(l), Value::Integer(r), BinOp::Plus) => Value::Float(l + *r as f64),
+            (Value::Integer(l), Value::Integer(r), BinOp::Minus) => {
+                Value::Integer(l.checked_sub(*r).ok_or_else(overflow)?)
+            }
+            (Value::Float(l), Value::Float(r), BinOp::Minus) => Value::Float(l - r),
+            (Value::Integer(l), Value::Integer(r), BinOp::Multiply) => {
+                Value::Integer(l.checked_mul(*r).ok_or_else(overflow)?)
+            }
+            (Value::Float(l), Value::Float(r), BinOp::Multiply) => Value::Float(l * r),
+            (Value::Integer(_), Value::Integer(0), BinOp::Divide | BinOp::Modulo) => {
+                return Err(RuntimeError::DivisionByZero { span })
+            }
+            (Value::Float(_), Value::Float(r), BinOp::Divide | BinOp::Modulo) if *r == 0.0 => {
+                return Err(RuntimeError::DivisionByZero { span })
+            }
+            (Value::Integer(l), Value::Integer(r), BinOp::Divide) => {
+                Value::Integer(l.checked_div(*r).ok_or_else(overflow)?)
+            }
+            (Value::Float(l), Value::Float(r), BinOp::Divide) => Value::Float(l / r),
+            (Value::Integer(l), Value::Integer(r), BinOp::Modulo) => {
+                Value::Integer(l.checked_rem(*r).ok_or_else(overflow)?)
+            }
+            (Value::Float(l), Value::Float(r), BinOp::Modulo) => Value::Float(l % r),
+            (Value::Integer(l), Value::Integer(r), BinOp::LessThan) => Value::Boolean(l < r),
+            (Value::Float(l), Value::Float(r), BinOp::LessThan) => Value::Boolean(l < r),
+            (Value::Integer(l), Value::Integer(r), BinOp::GreaterThan) => Value::Boolean(l > r),
+            (Value::Float(l), Value::Float(r), BinOp::GreaterThan) => Value::Boolean(l > r),
+            (Value::Integer(l), Value::Integer(r), BinOp::LessThanOrEqual) => {
+                Value::Boolean(l <= r)
+            }
+            (Value::Float(l), Value::Float(r), BinOp::LessThanOrEqual) => Value::Boolean(l <= r),
+            (Value::Integer(l), Value::Integer(r), BinOp::GreaterThanOrEqual) => {
+                Value::Boolean(l >= r)
+            }
+            (Value::Float(l), Value::Float(r), BinOp::GreaterThanOrEqual) => Value::Boolean(l >= r),
+            (Value::Integer(l), Value::Integer(r), BinOp::Equals) => Value::Boolean(l == r),
+            (Value::Float(l), Value::Float(r), BinOp::Equals) => Value::Boolean(l == r),
+            (Value::String(l), Value::String(r), BinOp::Equals) => Value::Boolean(l == r),
+            (Value::Boolean(l), Value::Boolean(r), BinOp::Equals) => Value::Boolean(l == r),
+            (Value::Integer(l), Value::Float(r), BinOp::Equals) => Value::Boolean(*l as f64 == *r),
+            (Value::Float(l), Value::Integer(r), BinOp::Equals) => Value::Boolean(*l == *r as f64),
+            (Value::Integer(l), Value::Integer(r), BinOp::NotEquals) => Value::Boolean(l != r),
+            (Value::Float(l), Value::Float(r), BinOp::NotEquals) => Value::Boolean(l != r),
+            (Value::String(l), Value::String(r), BinOp::NotEquals) => Value::Boolean(l != r),
+            (Value::Boolean(l), Value::Boolean(r), BinOp::NotEquals) => Value::Boolean(l != r),
+            (Value::Integer(l), Value::Float(r), BinOp::NotEquals) => {
+                Value::Boolean(*l as f64 != *r)
+            }
+            (Value::Float(l), Value::Integer(r), BinOp::NotEquals) => {
+                Value::Boolean(*l != *r as f64)
+            }
+            (Value::List(items), Value::Integer(i), BinOp::Index) => {
+                let items = items.borrow();
+                items[list_index(*i, items.len(), span)?].clone()
+            }
+            (Value::Kosh(entries), key, BinOp::Index) => {
+                let key = Key::from_value(key).ok_or(RuntimeError::InvalidKey {
+                    found: key.type_name(),
+                    span,
+                })?;
+                match entries.borrow().get(&key) {
+                    Some(value) => value.clone(),
+                    None => {
+                        return Err(RuntimeError::MissingKey {
+                            key: key.to_string(),
+                            span,
+                        })
+                    }
+                }
+            }
+            (value, Value::Kosh(entries), BinOp::Contains) => Value::Boolean(
+                Key::from_value(value).is_some_and(|key| entries.borrow().contains_key(&key)),
+            ),
+            (value, Value::List(items), BinOp::Contains) => {
+                Value::Boolean(items.borrow().iter().any(|item| values_equal(item, value)))
+            }
+            (Value::String(part), Value::String(text), BinOp::Contains) => {
+                Value::Boolean(text.contains(part.as_str()))
+            }
+            (Value::String(text), Value::Integer(i), BinOp::Index) => {
+                let length = text.chars().count();
+                let index = list_index(*i, length, span)?;
+                Value::String(text.chars().nth(index).unwrap().to_string())
+            }
+            _ => {
+                return Err(RuntimeError::TypeMismatch {
+                    op: op.clone(),
+                    left: left.type_name(),
+                    right: right.type_name(),
+                    span,
+                })
+            }
+        };
+        Ok(value)
+    }
+
+    fn exec_stmt(&mut self, stmt: &Statement) -> Result<Flow, RuntimeError> {
+        match &stmt.kind {
+            StatementKind::Declaration(name, expr) | StatementKind::Constant(name, expr) => {
+                let value = self.eval_expr(expr)?;
+                self.declare(name, value);
             }
 
-            Statement::Assignment(name, expr) => {
-                self.env.get(name).unwrap_or_else(|| {
-                    println!("Identifier {:?} not found.", name);
-                    exit(0);
-                });
+            StatementKind::Assignment(name, expr) => {
+                if Scope::get(&self.env, name).is_none() {
+                    return Err(RuntimeError::UndeclaredAssignment {
+                        name: name.clone(),
+                        span: stmt.span,
+                    });
+                }
+
+                let value = self.eval_expr(expr)?;
 
-                let value = self.eval_expr(expr);
-                
-                self.env.insert(name.clone(), value);
+                Scope::set(&self.env, name, value);
             }
 
-            Statement::If {
+            StatementKind::IndexAssignment { list, index, value } => match self.eval_expr(list)? {
+                Value::Kosh(entries) => {
+                    let key = self.eval_key(index)?;
+                    let value = self.eval_expr(value)?;
+                    entries.borrow_mut().insert(key, value);
+                }
+                Value::List(items) => {
+                    let i = self.eval_index(index)?;
+                    let value = self.eval_expr(value)?;
+
+                    let mut items = items.borrow_mut();
+                    let i = list_index(i, items.len(), index.span)?;
+                    items[i] = value;
+                }
+                other => return Err(not_a_collection(&other, list.span)),
+            },
+            StatementKind::FieldAssignment {
+                record,
+                field,
+                value,
+            } => {
+                let record = self.eval_record(record)?;
+                let value = self.eval_expr(value)?;
+
+                let mut record = record.borrow_mut();
+                let name = record.name.clone();
+                match record.fields.iter_mut().find(|(name, _)| name == field) {
+                    Some((_, slot)) => *slot = value,
+                    None => {
+                        return Err(RuntimeError::UnknownField {
+                            record: name,
+                            field: field.clone(),
+                            span: stmt.span,
+                        })
+                    }
+                }
+            }
+            StatementKind::StructDef(name, fields) => {
+                self.structs.insert(name.clone(), fields.clone());
+            }
+            StatementKind::Append { list, value } => {
+                let items = self.eval_list(list)?;
+                let value = self.eval_expr(value)?;
+                items.borrow_mut().push(value);
+            }
+            StatementKind::Remove { list, index } => match self.eval_expr(list)? {
+                Value::Kosh(entries) => {
+                    let key = self.eval_key(index)?;
+                    if entries.borrow_mut().remove(&key).is_none() {
+                        return Err(RuntimeError::MissingKey {
+                            key: key.to_string(),
+                            span: index.span,
+                        });
+                    }
+                }
+                Value::List(items) => {
+                    let i = self.eval_index(index)?;
+
+                    let mut items = items.borrow_mut();
+                    let i = list_index(i, items.len(), index.span)?;
+                    items.remove(i);
+                }
+                other => return Err(not_a_collection(&other, list.span)),
+            },
+
+            StatementKind::If {
                 condition,
                 then_block,
                 else_block,
             } => {
-                let cond = self.eval_expr(condition);
+                let cond = self.eval_expr(condition)?;
                 if self.is_truthy(&cond) {
-                    for stmt in then_block {
-                        self.exec_stmt(stmt);
-                    }
+                    return self.exec_scoped(then_block, Scope::child(&self.env));
                 } else {
-                    for stmt in else_block {
-                        self.exec_stmt(stmt);
+                    return self.exec_scoped(else_block, Scope::child(&self.env));
+                }
+            }
+            StatementKind::Match {
+                value,
+                cases,
+                default,
+            } => {
+                let value = self.eval_expr(value)?;
+                for (case, body) in cases {
+                    let matched = match case {
+                        Case::Value(case) => values_equal(&value, &self.eval_expr(case)?),
+                        Case::Range(start, end) => {
+                            let start = self.eval_expr(start)?;
+                            let end = self.eval_expr(end)?;
+                            in_range(&value, &start, &end)
+                        }
+                    };
+                    if matched {
+                        return self.exec_scoped(body, Scope::child(&self.env));
                     }
                 }
+                return self.exec_scoped(default, Scope::child(&self.env));
+            }
+            StatementKind::Try {
+                body,
+                variable,
+                handler,
+            } => {
+                let error = match self.exec_scoped(body, Scope::child(&self.env)) {
+                    Ok(flow) => return Ok(flow),
+                    Err(error) => error,
+                };
+                let scope = Scope::child(&self.env);
+                let error = self.error_value(error);
+                scope.borrow_mut().vars.insert(variable.clone(), error);
+                return self.exec_scoped(handler, scope);
             }
-            Statement::While { condition, body } => {
-                let mut eval = self.eval_expr(condition);
+            StatementKind::Throw(value) => {
+                return Err(RuntimeError::Thrown {
+                    value: self.eval_expr(value)?,
+                    span: stmt.span,
+                });
+            }
+            StatementKind::While { condition, body } => {
+                let mut eval = self.eval_expr(condition)?;
                 while self.is_truthy(&eval) {
-                    for stmt in body {
-                        self.exec_stmt(stmt);
+                    match self.exec_scoped(body, Scope::child(&self.env))? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Normal | Flow::Continue => {}
                     }
-                    eval = self.eval_expr(condition);
+                    eval = self.eval_expr(condition)?;
                 }
             }
-            Statement::Print(expr) => {
-                let value = self.eval_expr(expr);
-                let data = match value {
-                    Value::String(x) => x.to_string(),
-                    Value::Boolean(x) => x.to_string(),
-                    Value::Float(x) => x.to_string(),
-                    Value::Integer(x) => x.to_string(),
-                    Value::None => "khali".to_string(),
+            StatementKind::For {
+                variable,
+                start,
+                end,
+                step,
+                body,
+            } => {
+                let mut current = self.eval_integer(start)?;
+                let end = self.eval_integer(end)?;
+                let step = match step {
+                    Some(step) => match self.eval_integer(step)? {
+                        0 => return Err(RuntimeError::ZeroStep { span: step.span }),
+                        step => step,
+                    },
+                    None if current <= end => 1,
+                    None => -1,
                 };
-                println!("{}", data);
+
+                while (step > 0 && current <= end) || (step < 0 && current >= end) {
+                    let scope = Scope::child(&self.env);
+                    scope
+                        .borrow_mut()
+                        .vars
+                        .insert(variable.clone(), Value::Integer(current));
+                    match self.exec_scoped(body, scope)? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                    current = match current.checked_add(step) {
+                        Some(next) => next,
+                        None => break,
+                    };
+                }
             }
-            Statement::Input(dtype, name) => {
-                print!("{} ({}) >>> ", dtype, name);
-                io::stdout().flush().unwrap_or_else(|data| {
-                    println!("{}", data);
-                    std::process::exit(1);
-                });
-                let mut input = String::new();
-                match io::stdin().read_line(&mut input) {
-                    Ok(_) => {
-                        let value = input.trim();
-
-                        let value = if dtype == "sankhya" {
-                            Value::Integer(value.parse().unwrap_or_else(|data| {
-                                println!("{}", data);
-                                std::process::exit(1);
-                            }))
-                        } else if dtype == "dasamlav" {
-                            Value::Float(value.parse().unwrap_or_else(|data| {
-                                println!("{}", data);
-                                std::process::exit(1);
-                            }))
-                        } else if dtype == "paath" {
-                            Value::String(value.to_string())
-                        } else if dtype == "tark" {
-                            Value::Boolean(value.parse().unwrap_or_else(|data| {
-                                println!("{}", data);
-                                std::process::exit(1);
-                            }))
-                        } else {
-                            println!("{}", dtype);
-                            panic!("Invalid data type")
-                        };
-
-                        self.env.insert(name.clone(), value);
+            StatementKind::ForEach {
+                variable,
+                iterable,
+                body,
+            } => {
+                // Iterate over a snapshot, so the body may change the list.
+                let items: Vec<Value> = match self.eval_expr(iterable)? {
+                    Value::List(items) => items.borrow().clone(),
+                    Value::Kosh(entries) => entries.borrow().keys().map(Key::to_value).collect(),
+                    Value::String(text) => {
+                        text.chars().map(|c| Value::String(c.to_string())).collect()
                     }
-                    Err(error) => {
-                        eprintln!("Error reading input: {}", error);
+                    other => {
+                        return Err(RuntimeError::WrongType {
+                            expected: "suchi",
+                            found: other.type_name(),
+                            span: iterable.span,
+                        })
+                    }
+                };
+
+                for item in items {
+                    let scope = Scope::child(&self.env);
+                    scope.borrow_mut().vars.insert(variable.clone(), item);
+                    match self.exec_scoped(body, scope)? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Normal | Flow::Continue => {}
                     }
                 }
-            },
-            Statement::FunctionDef(name, params, body, return_expression) => {
-                let params = params.iter().map(|param| match param {
-                    Expression::Identifier(ident) => ident.clone(),
-                    _ => panic!("Invalid parameter"),
-                }).collect::<Vec<String>>();
-                
-                self.functions.insert(name.clone(), (params, body.to_vec(), return_expression.clone()));
-            },
-            Statement::FunctionCall(func_name, args, return_var) => {
-                let (params, body, return_stmt) = self.functions.get(func_name).unwrap().clone();
+            }
+            StatementKind::Break => return Ok(Flow::Break),
+            StatementKind::Continue => return Ok(Flow::Continue),
+            StatementKind::Return(value) => {
+                let value = match value {
+                    Some(expr) => self.eval_expr(expr)?,
+                    None => Value::None,
+                };
+                return Ok(Flow::Return(value));
+            }
+            StatementKind::Print(expr) => {
+                let value = self.eval_expr(expr)?;
+                println!("{}", value);
+            }
+            StatementKind::Input(dtype, name) => {
+                let io_error = |error: io::Error| RuntimeError::Io {
+                    message: error.to_string(),
+                    span: stmt.span,
+                };
 
-                // Create a new scope for the function
-                let mut local_variables = self.env.clone();
+                print!("{} ({}) >>> ", dtype, name);
+                io::stdout().flush().map_err(io_error)?;
 
-                for (param, arg) in params.iter().zip(args.iter()) {
-                    local_variables.insert(param.clone(), self.eval_expr(arg));
+                let mut input = String::new();
+                match &mut self.input {
+                    Some(reader) => reader.read_line(&mut input),
+                    None => io::stdin().read_line(&mut input),
                 }
-        
-                let result = self.execute_block(body, local_variables, return_stmt);
-                
-                self.env.insert(return_var.to_string(), result);
+                .map_err(io_error)?;
+                let value = input.trim();
 
+                let parse_error = || RuntimeError::InputParse {
+                    input: value.to_string(),
+                    data_type: dtype.clone(),
+                    span: stmt.span,
+                };
+
+                let value = match dtype.as_str() {
+                    "sankhya" | "संख्या" => {
+                        Value::Integer(value.parse().map_err(|_| parse_error())?)
+                    }
+                    "dasamlav" | "दशमलव" => {
+                        Value::Float(value.parse().map_err(|_| parse_error())?)
+                    }
+                    "paath" | "पाठ" => Value::String(value.to_string()),
+                    "tark" | "तर्क" => {
+                        Value::Boolean(value.parse().map_err(|_| parse_error())?)
+                    }
+                    _ => {
+                        return Err(RuntimeError::UnknownInputType {
+                            data_type: dtype.clone(),
+                            span: stmt.span,
+                        })
+                    }
+                };
+
+                self.store(name, value);
+            }
+            StatementKind::FunctionDef(name, params, body) => {
+                let function = self.make_function(Some(name), params, body);
+                self.declare(name, function);
+            }
+            StatementKind::FunctionCall(func_name, args, return_var) => {
+                let result = self.call_function(func_name, args, stmt.span)?;
+
+                self.store(return_var, result);
+            }
+            StatementKind::Expression(expr) => {
+                self.eval_expr(expr)?;
             }
         }
+        Ok(Flow::Normal)
     }
-    
 
-    fn execute_block(&mut self, body: Vec<Statement>, local_variables: HashMap<String, Value>, return_stmt: Expression) -> Value {
-        let original_scope = self.env.clone();
-        self.env = local_variables;
+    fn call_function(
+        &mut self,
+        func_name: &str,
+        args: &[Expression],
+        span: Span,
+    ) -> Result<Value, RuntimeError> {
+        let function = match Scope::get(&self.env, func_name) {
+            Some(Value::Function(function)) => function,
+            Some(value) => {
+                return Err(RuntimeError::WrongType {
+                    expected: "kaam",
+                    found: value.type_name(),
+                    span,
+                })
+            }
+            None => {
+                return Err(RuntimeError::UndefinedFunction {
+                    name: func_name.to_string(),
+                    span,
+                })
+            }
+        };
 
-        for statement in body {
-            self.exec_stmt(&statement);
+        if function.params.len() != args.len() {
+            return Err(RuntimeError::ArgumentCount {
+                name: func_name.to_string(),
+                expected: function.params.len(),
+                found: args.len(),
+                span,
+            });
         }
 
-        let result = self.eval_expr(&return_stmt);
+        // The body runs in a fresh scope inside the one the function was
+        // made in, not the one it is called from.
+        let local_variables = Scope::child(&function.closure);
 
-        self.env = original_scope;
+        for (param, arg) in function.params.iter().zip(args.iter()) {
+            let value = self.eval_expr(arg)?;
+            local_variables
+                .borrow_mut()
+                .vars
+                .insert(param.clone(), value);
+        }
 
+        if self.depth == MAX_CALL_DEPTH {
+            return Err(RuntimeError::CallDepth {
+                name: func_name.to_string(),
+                span,
+            });
+        }
+        self.depth += 1;
+        let result = stacker::maybe_grow(STACK_RED_ZONE, STACK_GROWTH, || {
+            self.execute_block(&function.body, local_variables)
+        });
+        self.depth -= 1;
         result
     }
-    
+
+    /// The `galti` record a `pakdo` receives: its `sandesh` (message) and
+    /// `prakar` (kind: the error code, or `fenko`), and the `maan` (value)
+    /// given to `fenko`, or khali.
+    fn error_value(&self, error: RuntimeError) -> Value {
+        let (message, kind, value) = match error {
+            RuntimeError::Thrown { value, .. } => (value.to_string(), "fenko", value),
+            error => {
+                let diagnostic = error.to_diagnostic(self.lang);
+                (diagnostic.message, diagnostic.code, Value::None)
+            }
+        };
+        Value::Record(Rc::new(RefCell::new(Record {
+            name: "galti".to_string(),
+            fields: vec![
+                ("sandesh".to_string(), Value::String(message)),
+                ("prakar".to_string(), Value::String(kind.to_string())),
+                ("maan".to_string(), value),
+            ],
+        })))
+    }
+
+    fn make_function(&self, name: Option<&String>, params: &[String], body: &[Statement]) -> Value {
+        Value::Function(Rc::new(Function {
+            name: name.cloned(),
+            params: params.to_vec(),
+            body: body.to_vec(),
+            closure: Rc::clone(&self.env),
+        }))
+    }
+
+    /// Creates `name` in the innermost scope, or overwrites it if that
+    /// scope already has it.
+    fn declare(&mut self, name: &str, value: Value) {
+        self.env.borrow_mut().vars.insert(name.to_string(), value);
+    }
+
+    /// Overwrites `name` where it already exists, or creates it in the
+    /// innermost scope.
+    fn store(&mut self, name: &str, value: Value) {
+        if !Scope::set(&self.env, name, value.clone()) {
+            self.declare(name, value);
+        }
+    }
+
+    /// Runs `statements` until one of them breaks out of the normal flow.
+    fn exec_block(&mut self, statements: &[Statement]) -> Result<Flow, RuntimeError> {
+        for stmt in statements {
+            let flow = self.exec_stmt(stmt)?;
+            if !matches!(flow, Flow::Normal) {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn eval_list(&mut self, expr: &Expression) -> Result<Rc<RefCell<Vec<Value>>>, RuntimeError> {
+        match self.eval_expr(expr)? {
+            Value::List(items) => Ok(items),
+            value => Err(RuntimeError::WrongType {
+                expected: "suchi",
+                found: value.type_name(),
+                span: expr.span,
+            }),
+        }
+    }
+
+    fn eval_record(&mut self, expr: &Expression) -> Result<Rc<RefCell<Record>>, RuntimeError> {
+        match self.eval_expr(expr)? {
+            Value::Record(record) => Ok(record),
+            value => Err(RuntimeError::WrongType {
+                expected: "dhancha",
+                found: value.type_name(),
+                span: expr.span,
+            }),
+        }
+    }
+
+    fn eval_key(&mut self, expr: &Expression) -> Result<Key, RuntimeError> {
+        let value = self.eval_expr(expr)?;
+        Key::from_value(&value).ok_or(RuntimeError::InvalidKey {
+            found: value.type_name(),
+            span: expr.span,
+        })
+    }
+
+    fn eval_index(&mut self, expr: &Expression) -> Result<i64, RuntimeError> {
+        match self.eval_expr(expr)? {
+            Value::Integer(index) => Ok(index),
+            value => Err(RuntimeError::WrongType {
+                expected: "sankhya",
+                found: value.type_name(),
+                span: expr.span,
+            }),
+        }
+    }
+
+    fn eval_integer(&mut self, expr: &Expression) -> Result<i64, RuntimeError> {
+        match self.eval_expr(expr)? {
+            Value::Integer(value) => Ok(value),
+            value => Err(RuntimeError::LoopBound {
+                found: value.type_name(),
+                span: expr.span,
+            }),
+        }
+    }
+
+    /// Runs a function body in `local_variables`, yielding the value of its
+    /// `wapas karo`, or khali if it finishes without one.
+    fn execute_block(
+        &mut self,
+        body: &[Statement],
+        local_variables: Env,
+    ) -> Result<Value, RuntimeError> {
+        self.exec_scoped(body, local_variables)
+            .map(|flow| match flow {
+                Flow::Return(value) => value,
+                _ => Value::None,
+            })
+    }
+
+    /// Runs `statements` with `scope` as the innermost scope, so whatever
+    /// they declare is gone once they finish.
+    fn exec_scoped(&mut self, statements: &[Statement], scope: Env) -> Result<Flow, RuntimeError> {
+        let original_scope = std::mem::replace(&mut self.env, scope);
+        let flow = self.exec_block(statements);
+        self.env = original_scope;
+        flow
+    }
 
     fn is_truthy(&self, value: &Value) -> bool {
         match value {
@@ -246,14 +1256,577 @@ impl Interpreter {
             Value::Integer(i) => *i != 0,
             Value::Float(f) => *f != 0.0,
             Value::String(s) => !s.is_empty(),
+            Value::List(items) => !items.borrow().is_empty(),
+            Value::Kosh(entries) => !entries.borrow().is_empty(),
+            Value::Record(_) | Value::Function(_) => true,
             Value::None => false,
         }
     }
 
-    pub fn run(&mut self, program: Program) {
+    /// Runs every statement of `program` in order, stopping at the first
+    /// runtime error.
+    pub fn run(&mut self, program: Program) -> Result<(), RuntimeError> {
         for stmt in program.statements {
-            // println!("{:?}", stmt);
-            self.exec_stmt(&stmt);
+            self.exec_stmt(&stmt)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks a zero-based `index` into a list or string of `length` elements.
+fn list_index(index: i64, length: usize, span: Span) -> Result<usize, RuntimeError> {
+    usize::try_from(index)
+        .ok()
+        .filter(|&i| i < length)
+        .ok_or(RuntimeError::IndexOutOfRange {
+            index,
+            length,
+            span,
+        })
+}
+
+fn not_a_collection(value: &Value, span: Span) -> RuntimeError {
+    RuntimeError::WrongType {
+        expected: "suchi",
+        found: value.type_name(),
+        span,
+    }
+}
+
+/// Works out an operation whose operands are all literals, the same way
+/// running it would, so the parser can put the result in its place. Gives
+/// `None` for anything else and for operations that fail, which are left
+/// to report their error when the program runs.
+pub(crate) fn fold(expr: &Expression) -> Option<ExpressionKind> {
+    fn is_literal(expr: &Expression) -> bool {
+        matches!(
+            expr.kind,
+            ExpressionKind::Integer(_)
+                | ExpressionKind::Float(_)
+                | ExpressionKind::String(_)
+                | ExpressionKind::Boolean(_)
+        )
+    }
+
+    let foldable = match &expr.kind {
+        ExpressionKind::BinaryOp(lhs, _, rhs) => is_literal(lhs) && is_literal(rhs),
+        ExpressionKind::Not(operand) => is_literal(operand),
+        ExpressionKind::Interpolated(parts) => parts.iter().all(|part| match part {
+            StringPart::Text(_) => true,
+            StringPart::Expression(part) => is_literal(part),
+        }),
+        _ => false,
+    };
+    if !foldable {
+        return None;
+    }
+
+    match Interpreter::new().eval_expr(expr).ok()? {
+        Value::Integer(i) => Some(ExpressionKind::Integer(i)),
+        Value::Float(f) => Some(ExpressionKind::Float(f)),
+        Value::String(s) => Some(ExpressionKind::String(s)),
+        Value::Boolean(b) => Some(ExpressionKind::Boolean(b)),
+        _ => None,
+    }
+}
+
+/// Whether `value` is a number between `start` and `end`, both included.
+/// Integers and decimals are compared by value.
+fn in_range(value: &Value, start: &Value, end: &Value) -> bool {
+    fn number(value: &Value) -> Option<f64> {
+        match value {
+            Value::Integer(i) => Some(*i as f64),
+            Value::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    match (value, start, end) {
+        (Value::Integer(value), Value::Integer(start), Value::Integer(end)) => {
+            start <= value && value <= end
+        }
+        _ => match (number(value), number(start), number(end)) {
+            (Some(value), Some(start), Some(end)) => start <= value && value <= end,
+            _ => false,
+        },
+    }
+}
+
+/// The equality of `hai`, also used by `me hai` and `milao`: integers and
+/// decimals are compared by value, other values of different types are
+/// never equal, and collections are compared by identity.
+fn values_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Integer(l), Value::Integer(r)) => l == r,
+        (Value::Float(l), Value::Float(r)) => l == r,
+        (Value::Integer(l), Value::Float(r)) => *l as f64 == *r,
+        (Value::Float(l), Value::Integer(r)) => *l == *r as f64,
+        (Value::String(l), Value::String(r)) => l == r,
+        (Value::Boolean(l), Value::Boolean(r)) => l == r,
+        (Value::List(l), Value::List(r)) => Rc::ptr_eq(l, r),
+        (Value::Kosh(l), Value::Kosh(r)) => Rc::ptr_eq(l, r),
+        (Value::Record(l), Value::Record(r)) => Rc::ptr_eq(l, r),
+        (Value::Function(l), Value::Function(r)) => Rc::ptr_eq(l, r),
+        (Value::None, Value::None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages;
+    use crate::parser::Parser;
+    use crate::tokens::Token;
+
+    fn run(source: &str) -> Result<Interpreter, RuntimeError> {
+        run_with_input(source, "")
+    }
+
+    fn run_with_input(source: &str, input: &str) -> Result<Interpreter, RuntimeError> {
+        let tokens = Token::tokenize(source).expect("source should lex");
+        let program = Parser::new(&tokens).parse().expect("source should parse");
+        let mut interpreter = Interpreter::new().with_input(io::Cursor::new(input.to_string()));
+        interpreter.run(program)?;
+        Ok(interpreter)
+    }
+
+    fn global(interpreter: &Interpreter, name: &str) -> Option<Value> {
+        Scope::get(&interpreter.env, name)
+    }
+
+    fn error(source: &str) -> RuntimeError {
+        run(source).err().expect("source should fail")
+    }
+
+    #[test]
+    fn runtime_errors_are_returned_with_what_went_wrong() {
+        assert!(matches!(
+            error("likho 1 satya ka yog"),
+            RuntimeError::TypeMismatch {
+                op: BinOp::Plus,
+                left: "sankhya",
+                right: "tark",
+                ..
+            }
+        ));
+        assert!(matches!(
+            error("likho y"),
+            RuntimeError::UndefinedVariable { name, .. } if name == "y"
+        ));
+        assert!(matches!(
+            error("likho chalao f par"),
+            RuntimeError::UndefinedFunction { name, .. } if name == "f"
+        ));
+        assert!(matches!(
+            error("banao f jo le a b fir aage\nlikho chalao f 1 par"),
+            RuntimeError::ArgumentCount {
+                expected: 2,
+                found: 1,
+                ..
+            }
+        ));
+        assert!(matches!(
+            error("likho 1 0 ka bhaag"),
+            RuntimeError::DivisionByZero { .. }
+        ));
+        assert!(matches!(
+            error("likho 9223372036854775807 1 ka yog"),
+            RuntimeError::Overflow {
+                op: BinOp::Plus,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn padho_reports_input_it_cannot_read() {
+        let error = run_with_input("padho sankhya x", "das\n").err().unwrap();
+        assert!(matches!(
+            error,
+            RuntimeError::InputParse { input, data_type, .. }
+                if input == "das" && data_type == "sankhya"
+        ));
+        let error = run_with_input("padho rang x", "laal\n").err().unwrap();
+        assert!(matches!(
+            error,
+            RuntimeError::UnknownInputType { data_type, .. } if data_type == "rang"
+        ));
+    }
+
+    #[test]
+    fn aur_and_ya_only_evaluate_the_right_side_when_needed() {
+        let interpreter = run("mano ginti barabar 0\n\
+                               banao jaancho jo le fir badlo ginti barabar ginti 1 ka yog wapas karo satya aage\n\
+                               mano a barabar asatya aur chalao jaancho par\n\
+                               mano b barabar satya ya chalao jaancho par\n\
+                               mano c barabar satya aur nhi chalao jaancho par\n\
+                               mano d barabar asatya aur 1 0 ka bhaag 0 hai")
+        .unwrap();
+        assert_eq!(global(&interpreter, "ginti"), Some(Value::Integer(1)));
+        assert_eq!(global(&interpreter, "a"), Some(Value::Boolean(false)));
+        assert_eq!(global(&interpreter, "b"), Some(Value::Boolean(true)));
+        assert_eq!(global(&interpreter, "c"), Some(Value::Boolean(false)));
+        assert_eq!(global(&interpreter, "d"), Some(Value::Boolean(false)));
+
+        assert!(matches!(
+            error("likho asatya ya 1 0 ka bhaag 0 hai"),
+            RuntimeError::DivisionByZero { .. }
+        ));
+    }
+
+    #[test]
+    fn warna_agar_runs_the_first_branch_whose_condition_holds() {
+        let interpreter = run("banao naapo jo le n fir\n\
+                                   agar n 0 se chota hai wapas karo \"rin\"\n\
+                                   warna agar n 0 hai wapas karo \"shunya\"\n\
+                                   warna agar n 100 se chota hai wapas karo \"chhota\"\n\
+                                   warna agar n 10 se bada hai wapas karo \"bada\"\n\
+                                   warna wapas karo \"kabhi nahi\"\n\
+                                   aage\n\
+                               aage\n\
+                               mano a barabar suchi chalao naapo -1 par chalao naapo 0 par chalao naapo 5 par chalao naapo 500 par aage\n\
+                               mano x barabar 0\n\
+                               agar asatya badlo x barabar 1 warna agar asatya badlo x barabar 2 aage")
+        .unwrap();
+        let a = global(&interpreter, "a").unwrap();
+        assert_eq!(a.to_string(), r#"["rin", "shunya", "chhota", "bada"]"#);
+        assert_eq!(global(&interpreter, "x"), Some(Value::Integer(0)));
+    }
+
+    #[test]
+    fn har_counts_up_down_and_by_kadam() {
+        let interpreter = run("mano a barabar suchi aage\n\
+                               har i 1 se 4 tak jodo a me i aage\n\
+                               mano b barabar suchi aage\n\
+                               har i 0 se 10 tak kadam 3 jodo b me i aage\n\
+                               mano c barabar suchi aage\n\
+                               har i 3 se 1 tak jodo c me i aage\n\
+                               mano d barabar suchi aage\n\
+                               har i 10 se 1 tak kadam -4 jodo d me i aage")
+        .unwrap();
+        let list = |name| global(&interpreter, name).unwrap().to_string();
+        assert_eq!(list("a"), "[1, 2, 3, 4]");
+        assert_eq!(list("b"), "[0, 3, 6, 9]");
+        assert_eq!(list("c"), "[3, 2, 1]");
+        assert_eq!(list("d"), "[10, 6, 2]");
+
+        assert!(matches!(
+            error("har i 1 se 5 tak kadam 0 likho i aage"),
+            RuntimeError::ZeroStep { .. }
+        ));
+    }
+
+    #[test]
+    fn ruko_and_agla_inside_agar_control_the_enclosing_loop() {
+        let interpreter = run("mano a barabar suchi aage\n\
+                               har i 1 se 20 tak\n\
+                                   agar i 2 % 0 hai agla aage\n\
+                                   agar i 7 se bada hai ruko aage\n\
+                                   jodo a me i\n\
+                               aage\n\
+                               mano b barabar suchi aage\n\
+                               mano n barabar 0\n\
+                               jabtak satya\n\
+                                   badlo n barabar n 1 ka yog\n\
+                                   agar n 2 % 0 hai agla aage\n\
+                                   agar n 7 se bada hai ruko aage\n\
+                                   jodo b me n\n\
+                               aage")
+        .unwrap();
+        let list = |name| global(&interpreter, name).unwrap().to_string();
+        assert_eq!(list("a"), "[1, 3, 5, 7]");
+        assert_eq!(list("b"), "[1, 3, 5, 7]");
+    }
+
+    #[test]
+    fn suchi_can_be_read_changed_grown_and_looped_over() {
+        let interpreter = run("mano l barabar suchi 10 20 30 aage\n\
+                               mano pehla barabar l 0 ka tatva\n\
+                               badlo l 1 ka tatva barabar 25\n\
+                               jodo l me 40\n\
+                               hatao l se 0\n\
+                               mano lambai barabar l ki lambai\n\
+                               mano kul barabar 0\n\
+                               har x l me badlo kul barabar kul x ka yog aage")
+        .unwrap();
+        assert_eq!(global(&interpreter, "pehla"), Some(Value::Integer(10)));
+        assert_eq!(
+            global(&interpreter, "l").unwrap().to_string(),
+            "[25, 30, 40]"
+        );
+        assert_eq!(global(&interpreter, "lambai"), Some(Value::Integer(3)));
+        assert_eq!(global(&interpreter, "kul"), Some(Value::Integer(95)));
+    }
+
+    #[test]
+    fn suchi_index_outside_the_list_is_an_error() {
+        for source in [
+            "mano l barabar suchi 1 2 aage\nlikho l 2 ka tatva",
+            "mano l barabar suchi 1 2 aage\nlikho l -1 ka tatva",
+            "mano l barabar suchi 1 2 aage\nbadlo l 5 ka tatva barabar 0",
+            "mano l barabar suchi aage\nhatao l se 0",
+        ] {
+            assert!(
+                matches!(error(source), RuntimeError::IndexOutOfRange { .. }),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn kosh_can_be_read_changed_and_looped_over_in_key_order() {
+        let interpreter = run(
+            "mano ank barabar kosh \"sita\" 95 \"ravi\" 80 7 \"saat\" aage\n\
+                               mano sita barabar ank \"sita\" ka tatva\n\
+                               badlo ank \"amit\" ka tatva barabar 70\n\
+                               badlo ank \"ravi\" ka tatva barabar 85\n\
+                               hatao ank se 7\n\
+                               mano hai_kya barabar \"sita\" ank me hai\n\
+                               mano nahi_kya barabar 7 ank me hai\n\
+                               mano naam barabar suchi aage\n\
+                               har n ank me jodo naam me n aage",
+        )
+        .unwrap();
+        assert_eq!(global(&interpreter, "sita"), Some(Value::Integer(95)));
+        assert_eq!(global(&interpreter, "hai_kya"), Some(Value::Boolean(true)));
+        assert_eq!(
+            global(&interpreter, "nahi_kya"),
+            Some(Value::Boolean(false))
+        );
+        assert_eq!(
+            global(&interpreter, "naam").unwrap().to_string(),
+            r#"["amit", "ravi", "sita"]"#
+        );
+        let ank = global(&interpreter, "ank").unwrap();
+        assert_eq!(ank.to_string(), r#"{"amit": 70, "ravi": 85, "sita": 95}"#);
+    }
+
+    #[test]
+    fn kosh_reports_missing_and_invalid_keys() {
+        assert!(matches!(
+            error("mano k barabar kosh \"a\" 1 aage\nlikho k \"b\" ka tatva"),
+            RuntimeError::MissingKey { key, .. } if key == "\"b\""
+        ));
+        assert!(matches!(
+            error("mano k barabar kosh aage\nhatao k se 1"),
+            RuntimeError::MissingKey { .. }
+        ));
+        assert!(matches!(
+            error("mano k barabar kosh satya 1 aage"),
+            RuntimeError::InvalidKey { found: "tark", .. }
+        ));
+    }
+
+    #[test]
+    fn naya_builds_a_record_whose_fields_can_be_read_and_changed() {
+        let interpreter = run("dhancha Chhatra naam umar aage\n\
+                               mano c barabar naya Chhatra \"Ravi\" 20 aage\n\
+                               mano naam barabar c ka naam\n\
+                               badlo c ki umar barabar c ki umar 1 ka yog")
+        .unwrap();
+        assert_eq!(
+            global(&interpreter, "naam"),
+            Some(Value::String("Ravi".to_string()))
+        );
+        let c = global(&interpreter, "c").unwrap();
+        assert_eq!(c.to_string(), r#"Chhatra { naam: "Ravi", umar: 21 }"#);
+    }
+
+    #[test]
+    fn records_report_wrong_field_counts_and_unknown_fields() {
+        assert!(matches!(
+            error("dhancha Bindu x y aage\nmano b barabar naya Bindu 1 aage"),
+            RuntimeError::FieldCount {
+                expected: 2,
+                found: 1,
+                ..
+            }
+        ));
+        assert!(matches!(
+            error("dhancha Bindu x y aage\nmano b barabar naya Bindu 1 2 aage\nlikho b ka z"),
+            RuntimeError::UnknownField { record, field, .. } if record == "Bindu" && field == "z"
+        ));
+        assert!(matches!(
+            error("dhancha Bindu x y aage\nmano b barabar naya Bindu 1 2 aage\nbadlo b ka z barabar 3"),
+            RuntimeError::UnknownField { .. }
+        ));
+        assert!(matches!(
+            error("mano b barabar naya Rekha 1 aage"),
+            RuntimeError::UndefinedStruct { name, .. } if name == "Rekha"
+        ));
+    }
+
+    #[test]
+    fn closures_keep_the_scope_they_were_made_in() {
+        let interpreter = run("banao ginti_banao jo le fir\n\
+                                   mano n barabar 0\n\
+                                   wapas karo banao jo le fir badlo n barabar n 1 ka yog wapas karo n aage\n\
+                               aage\n\
+                               mano pehli barabar chalao ginti_banao par\n\
+                               mano doosri barabar chalao ginti_banao par\n\
+                               chalao pehli par x me\n\
+                               chalao pehli par x me\n\
+                               chalao doosri par y me")
+        .unwrap();
+        assert_eq!(global(&interpreter, "x"), Some(Value::Integer(2)));
+        assert_eq!(global(&interpreter, "y"), Some(Value::Integer(1)));
+        assert_eq!(global(&interpreter, "n"), None);
+    }
+
+    #[test]
+    fn functions_can_be_passed_around_and_called_through_a_variable() {
+        let interpreter = run(
+            "banao do_baar jo le f n fir wapas karo chalao f chalao f n par par aage\n\
+                               mano dugna barabar banao jo le x fir wapas karo x 2 ka guna aage\n\
+                               mano z barabar chalao do_baar dugna 3 par\n\
+                               banao teen jo le fir wapas karo 3 aage\n\
+                               mano t barabar teen\n\
+                               mano w barabar chalao t par",
+        )
+        .unwrap();
+        assert_eq!(global(&interpreter, "z"), Some(Value::Integer(12)));
+        assert_eq!(global(&interpreter, "w"), Some(Value::Integer(3)));
+    }
+
+    #[test]
+    fn pakdo_receives_the_message_kind_and_value_of_the_error() {
+        let interpreter = run("mano galat barabar 0\n\
+                               koshish likho 1 0 ka bhaag pakdo e badlo galat barabar e aage\n\
+                               mano fenka barabar 0\n\
+                               koshish fenko suchi 1 2 aage pakdo e badlo fenka barabar e aage")
+        .unwrap();
+        let field = |name, field: &str| match global(&interpreter, name) {
+            Some(Value::Record(record)) => record
+                .borrow()
+                .fields
+                .iter()
+                .find(|(name, _)| name == field)
+                .map(|(_, value)| value.to_string())
+                .unwrap(),
+            other => panic!("`{}` is not a galti: {:?}", name, other),
+        };
+        assert_eq!(
+            field("galat", "sandesh"),
+            messages::text(Language::English, "E0305")
+        );
+        assert_eq!(field("galat", "prakar"), "E0305");
+        assert_eq!(field("galat", "maan"), "khali");
+        assert_eq!(field("fenka", "sandesh"), "[1, 2]");
+        assert_eq!(field("fenka", "prakar"), "fenko");
+        assert_eq!(field("fenka", "maan"), "[1, 2]");
+    }
+
+    #[test]
+    fn ruko_agla_and_wapas_karo_pass_through_koshish() {
+        let interpreter = run("mano a barabar suchi aage\n\
+                               har i 1 se 10 tak\n\
+                                   koshish\n\
+                                       agar i 2 hai agla aage\n\
+                                       agar i 4 hai ruko aage\n\
+                                       jodo a me i\n\
+                                   pakdo e jodo a me e aage\n\
+                               aage\n\
+                               banao f jo le fir koshish wapas karo 1 pakdo e aage wapas karo 2 aage\n\
+                               mano x barabar chalao f par")
+        .unwrap();
+        assert_eq!(global(&interpreter, "a").unwrap().to_string(), "[1, 3]");
+        assert_eq!(global(&interpreter, "x"), Some(Value::Integer(1)));
+    }
+
+    #[test]
+    fn call_result_updates_an_existing_variable_or_creates_a_local() {
+        let interpreter = run("mano x barabar 1\n\
+                               banao f jo le a fir wapas karo a aage\n\
+                               agar satya chalao f 5 par x me aage\n\
+                               agar satya chalao f 6 par y me aage")
+        .unwrap();
+        assert_eq!(global(&interpreter, "x"), Some(Value::Integer(5)));
+        assert_eq!(global(&interpreter, "y"), None);
+    }
+
+    #[test]
+    fn padho_inside_a_loop_updates_the_loop_condition() {
+        let interpreter = run_with_input(
+            "mano x barabar 0\n\
+             mano baar barabar 0\n\
+             jabtak x 5 nhi hai padho sankhya x badlo baar barabar baar 1 ka yog aage",
+            "3\n5\n",
+        )
+        .unwrap();
+        assert_eq!(global(&interpreter, "x"), Some(Value::Integer(5)));
+        assert_eq!(global(&interpreter, "baar"), Some(Value::Integer(2)));
+    }
+
+    #[test]
+    fn badlo_updates_the_nearest_outer_variable() {
+        let interpreter = run("mano kul barabar 0\n\
+                               banao badhao jo le n fir badlo kul barabar kul n ka yog aage\n\
+                               har i 1 se 4 tak chalao badhao i par aage")
+        .unwrap();
+        assert_eq!(global(&interpreter, "kul"), Some(Value::Integer(10)));
+    }
+
+    #[test]
+    fn collection_that_contains_itself_prints_once() {
+        let interpreter = run("mano a barabar suchi 1 aage\n\
+                               jodo a me a")
+        .unwrap();
+        let a = global(&interpreter, "a").unwrap();
+        assert_eq!(a.to_string(), "[1, [...]]");
+    }
+
+    #[test]
+    fn interpolation_fills_in_the_value_of_each_expression() {
+        let interpreter = run("mano naam barabar \"Ravi\"\n\
+                               mano umar barabar 20\n\
+                               mano l barabar suchi 1 \"do\" aage\n\
+                               mano s barabar \"{naam} ki umar {umar 1 ka yog} hai, {l} \\{naam\\}\"")
+        .unwrap();
+        assert_eq!(
+            global(&interpreter, "s"),
+            Some(Value::String(
+                "Ravi ki umar 21 hai, [1, \"do\"] {naam}".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn nested_text_is_written_with_every_escape_the_lexer_reads() {
+        let interpreter = run("mano a barabar suchi \"\\\"\\\\\\n\\t\\r\\0\\{x\\}\" aage").unwrap();
+        let a = global(&interpreter, "a").unwrap();
+        assert_eq!(a.to_string(), r#"["\"\\\n\t\r\0\{x\}"]"#);
+    }
+
+    #[test]
+    fn endless_recursion_is_an_error_koshish_can_catch() {
+        let interpreter = run("banao f jo le n fir wapas karo chalao f n par aage\n\
+                               mano prakar barabar \"\"\n\
+                               koshish likho chalao f 1 par\n\
+                               pakdo e badlo prakar barabar e ka prakar aage")
+        .unwrap();
+        let kind = global(&interpreter, "prakar").map(|kind| kind.to_string());
+        assert_eq!(kind.as_deref(), Some("E0321"));
+    }
+
+    #[test]
+    fn milao_matches_numbers_by_value() {
+        let interpreter = run("mano x barabar 0\n\
+                               milao 1 jab 1.0 fir badlo x barabar 1 aage\n\
+                               milao 2.0 jab 2 fir badlo x barabar x 2 ka yog aage")
+        .unwrap();
+        assert_eq!(global(&interpreter, "x"), Some(Value::Integer(3)));
+    }
+
+    #[test]
+    fn hai_and_me_hai_compare_numbers_by_value() {
+        let interpreter = run("mano a barabar 1 1.0 hai\n\
+                               mano b barabar 2.5 2 nhi hai\n\
+                               mano c barabar 1 (suchi 1.0 aage) me hai")
+        .unwrap();
+        for name in ["a", "b", "c"] {
+            assert_eq!(global(&interpreter, name), Some(Value::Boolean(true)));
         }
     }
 }
@@ -1,8 +1,17 @@
-mod tokens;
 mod ast;
-mod parser;
+mod checker;
+mod diagnostics;
+mod dialect;
 mod interpreter;
+pub mod messages;
+mod parser;
+mod span;
+mod tokens;
 
-pub use crate::interpreter::Interpreter;
-pub use crate::parser::Parser;
-pub use crate::tokens::Token;
\ No newline at end of file
+pub use crate::diagnostics::{Diagnostic, Severity, ToDiagnostic};
+pub use crate::dialect::{Dialect, DialectError};
+pub use crate::interpreter::{Function, Interpreter, Key, Record, RuntimeError, Value};
+pub use crate::messages::Language;
+pub use crate::parser::{Expected, ParseError, ParseWarning, Parser};
+pub use crate::span::Span;
+pub use crate::tokens::{LexError, LexErrorKind, Token};
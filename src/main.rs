@@ -1,45 +1,143 @@
-use bhasha::{Token, Parser, Interpreter};
+use bhasha::{
+    messages, Diagnostic, Dialect, DialectError, Interpreter, Language, Parser, ToDiagnostic, Token,
+};
 use std::env;
 use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::exit;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("Usage: {} <filename>", args[0]);
-        return;
+
+    let mut color = std::io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+    let mut lang = Language::from_env().unwrap_or_default();
+    let mut boli = None;
+    let mut filename = None;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--color" => color = true,
+            "--no-color" => color = false,
+            "--lang" => {
+                let code = rest.next().map(String::as_str).unwrap_or("");
+                lang = Language::from_code(code).unwrap_or_else(|| {
+                    eprintln!(
+                        "{}",
+                        messages::format(lang, "unknown-language", &[("code", &code)])
+                    );
+                    exit(1);
+                });
+            }
+            "--boli" => boli = rest.next().map(PathBuf::from),
+            _ => filename = Some(arg),
+        }
     }
 
-    let filename = &args[1];
+    let Some(filename) = filename else {
+        eprintln!(
+            "{}",
+            messages::format(lang, "usage", &[("program", &args[0])])
+        );
+        return;
+    };
+
     if !filename.ends_with(".bhasha") {
-        eprintln!("Error: Invalid file format. Only .bhasha files are supported.");
+        eprintln!("{}", messages::text(lang, "invalid-extension"));
         return;
     }
 
     let source = match fs::read_to_string(filename) {
         Ok(content) => content,
         Err(err) => {
-            eprintln!("Error reading file: {}", err);
+            eprintln!(
+                "{}",
+                messages::format(lang, "read-error", &[("error", &err)])
+            );
             return;
         }
     };
 
-    let source = format!("{} samapt", source);
-    
+    // A dialect given on the command line wins over one named by the file.
+    let boli = boli.or_else(|| {
+        Dialect::directive(&source).map(|path| {
+            Path::new(filename)
+                .parent()
+                .unwrap_or(Path::new(""))
+                .join(path)
+        })
+    });
+    let dialect = match boli {
+        Some(path) => Dialect::load(&path).unwrap_or_else(|error| {
+            let path = path.display();
+            let message = match &error {
+                DialectError::Io { message, .. } => {
+                    messages::format(lang, "dialect.io", &[("path", &path), ("message", message)])
+                }
+                DialectError::Syntax { line } => {
+                    messages::format(lang, "dialect.syntax", &[("path", &path), ("line", line)])
+                }
+                DialectError::NotKeyword { line, text } => messages::format(
+                    lang,
+                    "dialect.not-keyword",
+                    &[("path", &path), ("line", line), ("text", text)],
+                ),
+            };
+            eprintln!("{}", message);
+            exit(1);
+        }),
+        None => Dialect::default(),
+    };
+
+    let report = |diagnostics: Vec<Diagnostic>| -> ! {
+        for diagnostic in &diagnostics {
+            eprintln!("{}", diagnostic.render(&source, filename, color, lang));
+        }
+        exit(1);
+    };
+
     // let lexer = Token::tokenize(&source);
     
     // for token in lexer {
     //     println!("{:?}", token);
     // }
     
-    let mut lexer = Token::tokenize(&source);
+    let tokens = match Token::tokenize(&source) {
+        Ok(tokens) => dialect.apply(&source, tokens),
+        Err(errors) => report(
+            errors
+                .iter()
+                .map(|error| error.to_diagnostic(lang))
+                .collect(),
+        ),
+    };
 
-    let mut parser = Parser::new(&mut lexer);
+    let mut parser = Parser::new(&tokens);
 
     let program = parser.parse();
+    let warnings = parser
+        .warnings()
+        .iter()
+        .map(|warning| warning.to_diagnostic(lang));
+    let program = match program {
+        Ok(program) => {
+            for warning in warnings {
+                eprintln!("{}", warning.render(&source, filename, color, lang));
+            }
+            program
+        }
+        Err(errors) => report(
+            errors
+                .iter()
+                .map(|error| error.to_diagnostic(lang))
+                .chain(warnings)
+                .collect(),
+        ),
+    };
 
-    println!("{:?}", program);
+    let mut interpreter = Interpreter::new().with_language(lang);
 
-    let mut interpreter = Interpreter::new();
-    
-    interpreter.run(program);
-}
\ No newline at end of file
+    if let Err(error) = interpreter.run(program) {
+        report(vec![error.to_diagnostic(lang)]);
+    }
+}
@@ -0,0 +1,774 @@
+// src/messages.rs
+
+use std::env;
+use std::fmt;
+
+/// The language used for error messages and other user-facing text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    /// Hindi written in the Latin script, like the keywords themselves.
+    Hinglish,
+    /// Hindi written in Devanagari.
+    Hindi,
+}
+
+impl Language {
+    pub fn from_code(code: &str) -> Option<Language> {
+        match code.to_lowercase().as_str() {
+            "en" | "english" => Some(Language::English),
+            "hinglish" => Some(Language::Hinglish),
+            "hi" | "hindi" => Some(Language::Hindi),
+            _ => None,
+        }
+    }
+
+    /// Reads the language from the `BHASHA_LANG` environment variable.
+    pub fn from_env() -> Option<Language> {
+        env::var("BHASHA_LANG")
+            .ok()
+            .and_then(|code| Language::from_code(&code))
+    }
+}
+
+/// Every message as `(key, English, Hinglish, Hindi)`. Diagnostics are keyed
+/// by their error code, with `.label`, `.help` and `.note` suffixes for the
+/// extra lines.
+const MESSAGES: &[(&str, &str, &str, &str)] = &[
+    // Diagnostic chrome
+    ("error", "error", "galti", "त्रुटि"),
+    ("warning", "warning", "chetavani", "चेतावनी"),
+    ("note", "note", "dhyan do", "ध्यान दें"),
+    ("help", "help", "sujhav", "सुझाव"),
+    ("file-ends-here", "file ends here", "file yahan khatam hoti hai", "फ़ाइल यहाँ ख़त्म होती है"),
+    // Things the parser can expect
+    ("expected.name", "a name", "koi naam", "कोई नाम"),
+    ("expected.value", "a value", "koi maan", "कोई मान"),
+    ("expected.statement", "a statement", "koi statement", "कोई वाक्य"),
+    ("found.end", "the end of the code", "code ka ant", "कोड का अंत"),
+    // Lexer
+    (
+        "E0101",
+        "unexpected character `{text}`",
+        "anjaan akshar `{text}`",
+        "अनजान अक्षर `{text}`",
+    ),
+    (
+        "E0101.label",
+        "not part of the language",
+        "yeh bhasha ka hissa nahi hai",
+        "यह भाषा का हिस्सा नहीं है",
+    ),
+    (
+        "E0101.help",
+        "remove it, or put it inside a string with `\"...\"`",
+        "ise hatao, ya `\"...\"` ke andar string mein rakho",
+        "इसे हटाओ, या `\"...\"` के अंदर स्ट्रिंग में रखो",
+    ),
+    ("E0102", "unterminated string", "string band nahi hui", "स्ट्रिंग बंद नहीं हुई"),
+    (
+        "E0102.label",
+        "string starts here",
+        "string yahan shuru hoti hai",
+        "स्ट्रिंग यहाँ शुरू होती है",
+    ),
+    (
+        "E0102.help",
+        "close the string with `\"` on the same line",
+        "string ko usi line par `\"` se band karo",
+        "स्ट्रिंग को उसी पंक्ति में `\"` से बंद करो",
+    ),
+    (
+        "E0103",
+        "invalid escape `{text}` in string",
+        "string mein galat escape `{text}`",
+        "स्ट्रिंग में ग़लत एस्केप `{text}`",
+    ),
+    ("E0103.label", "unknown escape", "anjaan escape", "अनजान एस्केप"),
+    (
+        "E0103.help",
+        "use one of `\\n`, `\\t`, `\\r`, `\\0`, `\\\"`, `\\\\`, `\\{`, `\\}` or `\\u{...}`",
+        "`\\n`, `\\t`, `\\r`, `\\0`, `\\\"`, `\\\\`, `\\{`, `\\}` ya `\\u{...}` mein se ek likho",
+        "`\\n`, `\\t`, `\\r`, `\\0`, `\\\"`, `\\\\`, `\\{`, `\\}` या `\\u{...}` में से एक लिखो",
+    ),
+    (
+        "E0104",
+        "number `{text}` is out of range",
+        "sankhya `{text}` seema se bahar hai",
+        "संख्या `{text}` सीमा से बाहर है",
+    ),
+    ("E0104.label", "too large", "bahut badi hai", "बहुत बड़ी है"),
+    (
+        "E0104.note",
+        "whole numbers must lie between -9223372036854775808 and 9223372036854775807, and decimals below about 1.8e308",
+        "poori sankhya -9223372036854775808 se 9223372036854775807 ke beech honi chahiye, aur dasamlav lagbhag 1.8e308 se kam",
+        "पूर्ण संख्या -9223372036854775808 से 9223372036854775807 के बीच होनी चाहिए, और दशमलव लगभग 1.8e308 से कम",
+    ),
+    (
+        "E0105",
+        "invalid number `{text}`",
+        "galat sankhya `{text}`",
+        "ग़लत संख्या `{text}`",
+    ),
+    (
+        "E0105.label",
+        "digits do not match the base",
+        "ank is aadhaar ke nahi hain",
+        "अंक इस आधार के नहीं हैं",
+    ),
+    (
+        "E0105.help",
+        "`0x` numbers use the digits 0-9 and a-f, `0b` numbers use only 0 and 1",
+        "`0x` sankhya mein 0-9 aur a-f, `0b` sankhya mein sirf 0 aur 1 aate hain",
+        "`0x` संख्या में 0-9 और a-f, `0b` संख्या में सिर्फ़ 0 और 1 आते हैं",
+    ),
+    (
+        "E0106",
+        "unclosed `{` in string",
+        "string mein `{` band nahi hua",
+        "स्ट्रिंग में `{` बंद नहीं हुआ",
+    ),
+    (
+        "E0106.label",
+        "this `{` is never closed",
+        "yeh `{` band nahi hota",
+        "यह `{` बंद नहीं होता",
+    ),
+    (
+        "E0106.help",
+        "close it with `}` on the same line, or write `\\{` for a plain brace",
+        "ise usi line par `}` se band karo, ya seedhe brace ke liye `\\{` likho",
+        "इसे उसी पंक्ति में `}` से बंद करो, या सीधे ब्रेस के लिए `\\{` लिखो",
+    ),
+    // Parser
+    (
+        "E0201",
+        "`{found}` cannot start a statement",
+        "`{found}` se statement shuru nahi ho sakta",
+        "`{found}` से वाक्य शुरू नहीं हो सकता",
+    ),
+    ("E0201.label", "expected a statement", "yahan statement chahiye", "यहाँ वाक्य चाहिए"),
+    (
+        "E0201.help",
+        "statements start with a keyword such as `mano`, `likho`, `agar` or `jabtak`",
+        "statement kisi keyword se shuru hota hai, jaise `mano`, `likho`, `agar` ya `jabtak`",
+        "वाक्य किसी कीवर्ड से शुरू होता है, जैसे `mano`, `likho`, `agar` या `jabtak`",
+    ),
+    (
+        "E0202",
+        "expected {expected}, found {found}",
+        "{expected} chahiye tha, {found} mila",
+        "{expected} चाहिए था, {found} मिला",
+    ),
+    ("E0202.label", "expected {expected}", "{expected} chahiye", "{expected} चाहिए"),
+    ("E0203", "block is never closed", "block kabhi band nahi hua", "खंड कभी बंद नहीं हुआ"),
+    (
+        "E0203.label",
+        "this block has no `aage`",
+        "is block ka `aage` nahi hai",
+        "इस खंड का `aage` नहीं है",
+    ),
+    (
+        "E0203.help",
+        "add `aage` where the block should end",
+        "jahan block khatam hona chahiye wahan `aage` likho",
+        "जहाँ खंड ख़त्म होना चाहिए वहाँ `aage` लिखो",
+    ),
+    (
+        "E0204",
+        "parameters of `{function}` are not closed with `fir`",
+        "`{function}` ke parameter `fir` se band nahi hue",
+        "`{function}` के पैरामीटर `fir` से बंद नहीं हुए",
+    ),
+    (
+        "E0204.label",
+        "expected `fir` or a parameter name",
+        "`fir` ya parameter ka naam chahiye",
+        "`fir` या पैरामीटर का नाम चाहिए",
+    ),
+    (
+        "E0204.help",
+        "parameters are plain names: `banao f jo le a b fir`",
+        "parameter sirf naam hote hain: `banao f jo le a b fir`",
+        "पैरामीटर सिर्फ़ नाम होते हैं: `banao f jo le a b fir`",
+    ),
+    (
+        "E0206",
+        "{count} values are left without an operator",
+        "{count} value bina operator ke reh gayi",
+        "{count} मान बिना संक्रिया के रह गए",
+    ),
+    (
+        "E0206.label",
+        "these values are never combined",
+        "yeh value kabhi judi nahi",
+        "ये मान कभी जुड़े नहीं",
+    ),
+    (
+        "E0206.help",
+        "an operator goes after its two values, as in `a b ka yog`",
+        "operator apni do value ke baad aata hai, jaise `a b ka yog`",
+        "संक्रिया अपने दो मानों के बाद आती है, जैसे `a b ka yog`",
+    ),
+    (
+        "E0207",
+        "`{op}` needs two values before it",
+        "`{op}` se pehle do value chahiye",
+        "`{op}` से पहले दो मान चाहिए",
+    ),
+    ("E0207.label", "not enough values", "value kam hain", "मान कम हैं"),
+    (
+        "E0207.help",
+        "write both values first: `a b {op}`",
+        "pehle dono value likho: `a b {op}`",
+        "पहले दोनों मान लिखो: `a b {op}`",
+    ),
+    (
+        "E0208",
+        "`{name}` is already declared in this block",
+        "`{name}` is block mein pehle se bana hua hai",
+        "`{name}` इस खंड में पहले से बना हुआ है",
+    ),
+    ("E0208.label", "declared again here", "yahan dobara banaya", "यहाँ दोबारा बनाया"),
+    (
+        "E0208.previous",
+        "first declared here",
+        "pehli baar yahan banaya",
+        "पहली बार यहाँ बनाया",
+    ),
+    (
+        "E0208.help",
+        "to change its value, write `badlo {name} barabar ...`",
+        "value badalne ke liye `badlo {name} barabar ...` likho",
+        "मान बदलने के लिए `badlo {name} barabar ...` लिखो",
+    ),
+    (
+        "E0209",
+        "`{keyword}` can only be used inside a loop",
+        "`{keyword}` sirf loop ke andar chal sakta hai",
+        "`{keyword}` सिर्फ़ लूप के अंदर चल सकता है",
+    ),
+    ("E0209.label", "not inside a loop", "loop ke bahar", "लूप के बाहर"),
+    (
+        "E0209.help",
+        "`ruko` and `agla` belong inside a `jabtak` or `har` loop",
+        "`ruko` aur `agla` ko `jabtak` ya `har` loop ke andar likho",
+        "`ruko` और `agla` को `jabtak` या `har` लूप के अंदर लिखो",
+    ),
+    (
+        "E0210",
+        "`wapas karo` can only be used inside a function",
+        "`wapas karo` sirf function ke andar chal sakta hai",
+        "`wapas karo` सिर्फ़ फ़ंक्शन के अंदर चल सकता है",
+    ),
+    ("E0210.label", "not inside a function", "function ke bahar", "फ़ंक्शन के बाहर"),
+    (
+        "E0211",
+        "cannot assign to this expression",
+        "is expression ko badla nahi ja sakta",
+        "इस व्यंजक को बदला नहीं जा सकता",
+    ),
+    (
+        "E0211.label",
+        "not a variable or list element",
+        "na variable hai na suchi ka tatva",
+        "न चर है न सूची का तत्व",
+    ),
+    (
+        "E0211.help",
+        "write `badlo x barabar ...` or `badlo l 0 ka tatva barabar ...`",
+        "`badlo x barabar ...` ya `badlo l 0 ka tatva barabar ...` likho",
+        "`badlo x barabar ...` या `badlo l 0 ka tatva barabar ...` लिखो",
+    ),
+    (
+        "E0212",
+        "a key in this kosh has no value",
+        "is kosh ki ek key ki koi value nahi hai",
+        "इस कोश की एक कुंजी का कोई मान नहीं है",
+    ),
+    ("E0212.label", "this key has no value", "is key ki value nahi", "इस कुंजी का मान नहीं"),
+    (
+        "E0212.help",
+        "write each key followed by its value: `kosh \"a\" 1 \"b\" 2 aage`",
+        "har key ke baad uski value likho: `kosh \"a\" 1 \"b\" 2 aage`",
+        "हर कुंजी के बाद उसका मान लिखो: `kosh \"a\" 1 \"b\" 2 aage`",
+    ),
+    (
+        "E0213",
+        "field `{name}` is listed more than once",
+        "field `{name}` ek se zyada baar likha hai",
+        "क्षेत्र `{name}` एक से ज़्यादा बार लिखा है",
+    ),
+    ("E0213.label", "listed again here", "yahan dobara likha", "यहाँ दोबारा लिखा"),
+    (
+        "E0213.previous",
+        "first listed here",
+        "pehli baar yahan likha",
+        "पहली बार यहाँ लिखा",
+    ),
+    (
+        "E0214",
+        "cannot change the constant `{name}`",
+        "constant `{name}` ko badla nahi ja sakta",
+        "स्थिर `{name}` को बदला नहीं जा सकता",
+    ),
+    ("E0214.label", "changed here", "yahan badla", "यहाँ बदला"),
+    (
+        "E0214.constant",
+        "declared with `sthir` here",
+        "yahan `sthir` se banaya",
+        "यहाँ `sthir` से बनाया",
+    ),
+    (
+        "E0214.help",
+        "declare `{name}` with `mano` instead of `sthir` if it needs to change",
+        "agar `{name}` ko badalna hai to use `sthir` ki jagah `mano` se banao",
+        "अगर `{name}` को बदलना है तो उसे `sthir` की जगह `mano` से बनाओ",
+    ),
+    (
+        "E0215",
+        "`{name}` is a constant and cannot be declared again",
+        "`{name}` constant hai, use dobara nahi banaya ja sakta",
+        "`{name}` स्थिर है, उसे दोबारा नहीं बनाया जा सकता",
+    ),
+    ("E0215.label", "declared again here", "yahan dobara banaya", "यहाँ दोबारा बनाया"),
+    (
+        "E0215.help",
+        "use a different name",
+        "koi doosra naam rakho",
+        "कोई दूसरा नाम रखो",
+    ),
+    (
+        "E0216",
+        "a `jab` case must be a fixed value",
+        "`jab` case ek pakki value honi chahiye",
+        "`jab` का मामला एक पक्का मान होना चाहिए",
+    ),
+    ("E0216.label", "not a fixed value", "pakki value nahi", "पक्का मान नहीं"),
+    (
+        "E0216.help",
+        "write a literal such as `jab 1`, `jab \"haan\"` or `jab satya`, or a range of numbers such as `jab 1 se 5 tak`",
+        "`jab 1`, `jab \"haan\"` ya `jab satya` jaisi value, ya `jab 1 se 5 tak` jaisi sankhyaon ki range likho",
+        "`jab 1`, `jab \"haan\"` या `jab satya` जैसा मान, या `jab 1 se 5 tak` जैसी संख्याओं की सीमा लिखो",
+    ),
+    (
+        "E0217",
+        "`milao` does not handle `{value}`",
+        "`milao` mein `{value}` ka case nahi hai",
+        "`milao` में `{value}` का मामला नहीं है",
+    ),
+    (
+        "E0217.label",
+        "`{value}` is not handled",
+        "`{value}` ka case nahi hai",
+        "`{value}` का मामला नहीं है",
+    ),
+    (
+        "E0217.help",
+        "add `jab {value} fir ...` or a `warna`",
+        "`jab {value} fir ...` ya `warna` jodo",
+        "`jab {value} fir ...` या `warna` जोड़ो",
+    ),
+    (
+        "W0201",
+        "this case can never match",
+        "yeh case kabhi match nahi hoga",
+        "यह मामला कभी मेल नहीं खाएगा",
+    ),
+    ("W0201.label", "unreachable case", "yahan tak kabhi nahi pahunchenge", "यहाँ तक कभी नहीं पहुँचेंगे"),
+    (
+        "W0201.previous",
+        "this case already matches its values",
+        "yeh case iski values pehle hi match kar leta hai",
+        "यह मामला इसके मान पहले ही मिला लेता है",
+    ),
+    (
+        "W0201.empty",
+        "the range is empty because it starts after it ends",
+        "range khaali hai kyunki woh khatam hone ke baad shuru hoti hai",
+        "सीमा खाली है क्योंकि वह खत्म होने के बाद शुरू होती है",
+    ),
+    (
+        "W0202",
+        "this `warna` can never run",
+        "yeh `warna` kabhi nahi chalega",
+        "यह `warna` कभी नहीं चलेगा",
+    ),
+    ("W0202.label", "unreachable", "yahan tak kabhi nahi pahunchenge", "यहाँ तक कभी नहीं पहुँचेंगे"),
+    (
+        "W0202.note",
+        "the cases above already handle both `satya` and `asatya`",
+        "upar ke case `satya` aur `asatya` dono sambhal lete hain",
+        "ऊपर के मामले `satya` और `asatya` दोनों सँभाल लेते हैं",
+    ),
+    // Interpreter
+    (
+        "E0301",
+        "cannot use `{op}` with {left} and {right}",
+        "`{op}` ko {left} aur {right} ke saath nahi laga sakte",
+        "`{op}` को {left} और {right} के साथ नहीं लगा सकते",
+    ),
+    (
+        "E0301.note",
+        "the left value is a {left} and the right value is a {right}",
+        "baayan maan {left} hai aur daayan maan {right} hai",
+        "बायाँ मान {left} है और दायाँ मान {right} है",
+    ),
+    (
+        "E0302",
+        "variable `{name}` is not defined",
+        "variable `{name}` bana hi nahi",
+        "चर `{name}` परिभाषित नहीं है",
+    ),
+    ("E0302.label", "not defined", "bana nahi hai", "परिभाषित नहीं"),
+    (
+        "E0302.help",
+        "declare it first with `mano {name} barabar ...`",
+        "pehle `mano {name} barabar ...` se banao",
+        "पहले `mano {name} barabar ...` से बनाओ",
+    ),
+    (
+        "E0303",
+        "function `{name}` is not defined",
+        "function `{name}` bana hi nahi",
+        "फ़ंक्शन `{name}` परिभाषित नहीं है",
+    ),
+    (
+        "E0303.help",
+        "define it first with `banao {name} jo le ... fir`",
+        "pehle `banao {name} jo le ... fir` se banao",
+        "पहले `banao {name} jo le ... fir` से बनाओ",
+    ),
+    (
+        "E0304",
+        "wrong number of arguments for `{name}`",
+        "`{name}` ko galat ginti mein argument diye",
+        "`{name}` को ग़लत गिनती में तर्क दिए",
+    ),
+    (
+        "E0304.label",
+        "expected {expected}, found {found}",
+        "{expected} chahiye the, {found} mile",
+        "{expected} चाहिए थे, {found} मिले",
+    ),
+    ("E0305", "division by zero", "shunya se bhaag", "शून्य से भाग"),
+    ("E0305.label", "the right value is zero", "daayan maan shunya hai", "दायाँ मान शून्य है"),
+    (
+        "E0305.help",
+        "check that the divisor is not zero before dividing",
+        "bhaag se pehle jaanch lo ki bhaajak shunya na ho",
+        "भाग देने से पहले जाँच लो कि भाजक शून्य न हो",
+    ),
+    (
+        "E0306",
+        "result of `{op}` is too large for a sankhya",
+        "`{op}` ka nateeja sankhya ke liye bahut bada hai",
+        "`{op}` का नतीजा sankhya के लिए बहुत बड़ा है",
+    ),
+    (
+        "E0306.note",
+        "a sankhya must lie between {min} and {max}",
+        "sankhya {min} aur {max} ke beech honi chahiye",
+        "sankhya {min} और {max} के बीच होनी चाहिए",
+    ),
+    (
+        "E0307",
+        "`{input}` is not a valid {data_type}",
+        "`{input}` sahi {data_type} nahi hai",
+        "`{input}` सही {data_type} नहीं है",
+    ),
+    (
+        "E0307.label",
+        "expected a {data_type} here",
+        "yahan {data_type} chahiye tha",
+        "यहाँ {data_type} चाहिए था",
+    ),
+    (
+        "E0308",
+        "unknown input type `{data_type}`",
+        "anjaan input prakar `{data_type}`",
+        "अनजान इनपुट प्रकार `{data_type}`",
+    ),
+    (
+        "E0308.help",
+        "use one of `sankhya`, `dasamlav`, `paath` or `tark`",
+        "`sankhya`, `dasamlav`, `paath` ya `tark` mein se ek likho",
+        "`sankhya`, `dasamlav`, `paath` या `tark` में से एक लिखो",
+    ),
+    ("E0309", "{message}", "{message}", "{message}"),
+    (
+        "E0310",
+        "cannot change `{name}`, which was never declared",
+        "`{name}` kabhi banaya hi nahi gaya, use badal nahi sakte",
+        "`{name}` कभी बनाया ही नहीं गया, उसे बदल नहीं सकते",
+    ),
+    (
+        "E0310.help",
+        "declare it first with `mano {name} barabar ...`",
+        "pehle `mano {name} barabar ...` se banao",
+        "पहले `mano {name} barabar ...` से बनाओ",
+    ),
+    (
+        "E0311",
+        "loop bounds must be sankhya, found {found}",
+        "loop ki seema sankhya honi chahiye, {found} mila",
+        "लूप की सीमा संख्या होनी चाहिए, {found} मिला",
+    ),
+    ("E0311.label", "this is a {found}", "yeh {found} hai", "यह {found} है"),
+    ("E0312", "loop step cannot be zero", "loop ka kadam shunya nahi ho sakta", "लूप का कदम शून्य नहीं हो सकता"),
+    (
+        "E0312.label",
+        "this step is zero",
+        "yeh kadam shunya hai",
+        "यह कदम शून्य है",
+    ),
+    (
+        "E0312.help",
+        "a loop that moves by 0 never reaches its end",
+        "0 se badhne wala loop kabhi khatam nahi hota",
+        "0 से बढ़ने वाला लूप कभी ख़त्म नहीं होता",
+    ),
+    (
+        "E0313",
+        "index {index} is out of range for a suchi of length {length}",
+        "index {index} lambai {length} ki suchi ke bahar hai",
+        "सूचकांक {index} लंबाई {length} की सूची के बाहर है",
+    ),
+    ("E0313.label", "no element here", "yahan koi tatva nahi", "यहाँ कोई तत्व नहीं"),
+    (
+        "E0313.note",
+        "indexes start at 0, so the last index is {last}",
+        "index 0 se shuru hote hain, isliye aakhri index {last} hai",
+        "सूचकांक 0 से शुरू होते हैं, इसलिए आख़िरी सूचकांक {last} है",
+    ),
+    (
+        "E0313.empty",
+        "this suchi is empty",
+        "yeh suchi khali hai",
+        "यह सूची ख़ाली है",
+    ),
+    (
+        "E0314",
+        "expected {expected}, found {found}",
+        "{expected} chahiye tha, {found} mila",
+        "{expected} चाहिए था, {found} मिला",
+    ),
+    ("E0314.label", "this is a {found}", "yeh {found} hai", "यह {found} है"),
+    (
+        "E0315",
+        "key {key} is not in the kosh",
+        "key {key} kosh mein nahi hai",
+        "कुंजी {key} कोश में नहीं है",
+    ),
+    (
+        "E0315.help",
+        "check with `key k me hai` before reading it",
+        "padhne se pehle `key k me hai` se jaanch lo",
+        "पढ़ने से पहले `key k me hai` से जाँच लो",
+    ),
+    (
+        "E0316",
+        "a kosh key must be a sankhya or paath, found {found}",
+        "kosh ki key sankhya ya paath honi chahiye, {found} mila",
+        "कोश की कुंजी संख्या या पाठ होनी चाहिए, {found} मिला",
+    ),
+    (
+        "E0317",
+        "dhancha `{name}` is not defined",
+        "dhancha `{name}` banaya nahi gaya",
+        "ढाँचा `{name}` बनाया नहीं गया",
+    ),
+    (
+        "E0317.help",
+        "declare it first with `dhancha {name} <fields> aage`",
+        "pehle `dhancha {name} <fields> aage` se banao",
+        "पहले `dhancha {name} <fields> aage` से बनाओ",
+    ),
+    (
+        "E0318",
+        "dhancha `{name}` has {expected} fields but {found} values were given",
+        "dhancha `{name}` mein {expected} field hain par {found} value di gayi",
+        "ढाँचा `{name}` में {expected} क्षेत्र हैं पर {found} मान दिए गए",
+    ),
+    (
+        "E0318.label",
+        "expected {expected} values",
+        "{expected} value chahiye",
+        "{expected} मान चाहिए",
+    ),
+    (
+        "E0319",
+        "dhancha `{record}` has no field `{field}`",
+        "dhancha `{record}` mein `{field}` field nahi hai",
+        "ढाँचा `{record}` में `{field}` क्षेत्र नहीं है",
+    ),
+    (
+        "E0320",
+        "uncaught `fenko`: {value}",
+        "`fenko` ko kisi ne nahi pakda: {value}",
+        "`fenko` को किसी ने नहीं पकड़ा: {value}",
+    ),
+    ("E0320.label", "thrown here", "yahan fenka", "यहाँ फेंका"),
+    (
+        "E0320.help",
+        "catch it with `koshish ... pakdo e ... aage`",
+        "ise `koshish ... pakdo e ... aage` se pakdo",
+        "इसे `koshish ... pakdo e ... aage` से पकड़ो",
+    ),
+    (
+        "E0321",
+        "calling `{name}` would nest more than {limit} calls",
+        "`{name}` ko bulane se {limit} se zyada call ek ke andar ek ho jayengi",
+        "`{name}` को बुलाने से {limit} से ज़्यादा कॉल एक के अंदर एक हो जाएँगी",
+    ),
+    (
+        "E0321.help",
+        "make sure a function that calls itself reaches a case where it stops",
+        "dekho ki khud ko bulane wala kaam kabhi rukta bhi hai",
+        "देखो कि ख़ुद को बुलाने वाला काम कभी रुकता भी है",
+    ),
+    // Command line
+    (
+        "usage",
+        "Usage: {program} [--lang en|hi|hinglish] [--boli <file>] [--color | --no-color] <filename>",
+        "Istemaal: {program} [--lang en|hi|hinglish] [--boli <file>] [--color | --no-color] <filename>",
+        "उपयोग: {program} [--lang en|hi|hinglish] [--boli <file>] [--color | --no-color] <filename>",
+    ),
+    (
+        "invalid-extension",
+        "Error: Invalid file format. Only .bhasha files are supported.",
+        "Galti: galat file. Sirf .bhasha files chalti hain.",
+        "त्रुटि: ग़लत फ़ाइल प्रारूप। सिर्फ़ .bhasha फ़ाइलें चलती हैं।",
+    ),
+    (
+        "read-error",
+        "Error reading file: {error}",
+        "File padhne mein galti: {error}",
+        "फ़ाइल पढ़ने में त्रुटि: {error}",
+    ),
+    (
+        "unknown-language",
+        "Error: unknown language `{code}`. Use en, hi or hinglish.",
+        "Galti: anjaan bhasha `{code}`. en, hi ya hinglish likho.",
+        "त्रुटि: अनजान भाषा `{code}`। en, hi या hinglish लिखो।",
+    ),
+    (
+        "dialect.io",
+        "Error reading dialect file {path}: {message}",
+        "Boli file {path} padhne mein galti: {message}",
+        "बोली फ़ाइल {path} पढ़ने में त्रुटि: {message}",
+    ),
+    (
+        "dialect.syntax",
+        "Error in dialect file {path}, line {line}: expected `phrase = keyword`",
+        "Boli file {path}, line {line} mein galti: `shabd = keyword` chahiye",
+        "बोली फ़ाइल {path}, पंक्ति {line} में त्रुटि: `शब्द = keyword` चाहिए",
+    ),
+    (
+        "dialect.not-keyword",
+        "Error in dialect file {path}, line {line}: `{text}` is not a keyword",
+        "Boli file {path}, line {line} mein galti: `{text}` keyword nahi hai",
+        "बोली फ़ाइल {path}, पंक्ति {line} में त्रुटि: `{text}` कीवर्ड नहीं है",
+    ),
+];
+
+/// Looks up the text for `key` in `lang`. Unknown keys are returned as-is so
+/// a missing translation shows up instead of panicking.
+pub fn text(lang: Language, key: &'static str) -> &'static str {
+    MESSAGES
+        .iter()
+        .find(|(k, ..)| *k == key)
+        .map(|(_, english, hinglish, hindi)| match lang {
+            Language::English => *english,
+            Language::Hinglish => *hinglish,
+            Language::Hindi => *hindi,
+        })
+        .unwrap_or(key)
+}
+
+/// Looks up `key` and fills each `{name}` placeholder from `args`. The
+/// message is read once from left to right, so a value that itself
+/// contains `{name}` is left as it is.
+pub fn format(lang: Language, key: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut rest = text(lang, key);
+    let mut message = String::new();
+    while let Some(open) = rest.find('{') {
+        message.push_str(&rest[..open]);
+        rest = &rest[open..];
+        let value = rest.find('}').and_then(|close| {
+            let (_, value) = args.iter().find(|(name, _)| *name == &rest[1..close])?;
+            Some((value, close))
+        });
+        match value {
+            Some((value, close)) => {
+                message.push_str(&value.to_string());
+                rest = &rest[close + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(message: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}'))
+            .map(|(name, _)| name)
+            .filter(|name| name.chars().all(|ch| ch.is_ascii_lowercase() || ch == '_'))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn language_codes_are_read_in_any_case() {
+        assert_eq!(Language::from_code("en"), Some(Language::English));
+        assert_eq!(Language::from_code("Hinglish"), Some(Language::Hinglish));
+        assert_eq!(Language::from_code("HI"), Some(Language::Hindi));
+        assert_eq!(Language::from_code("hindi"), Some(Language::Hindi));
+        assert_eq!(Language::from_code("fr"), None);
+    }
+
+    #[test]
+    fn every_key_is_translated_once() {
+        for (index, (key, english, hinglish, hindi)) in MESSAGES.iter().enumerate() {
+            assert!(
+                !english.is_empty() && !hinglish.is_empty() && !hindi.is_empty(),
+                "`{}` is missing a translation",
+                key
+            );
+            assert!(
+                placeholders(english) == placeholders(hinglish)
+                    && placeholders(english) == placeholders(hindi),
+                "`{}` has different placeholders in its translations",
+                key
+            );
+            assert!(
+                MESSAGES[..index].iter().all(|(other, ..)| other != key),
+                "`{}` appears twice",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn values_are_not_filled_in_again() {
+        let message = format(
+            Language::English,
+            "E0307",
+            &[("input", &"{data_type}"), ("data_type", &"sankhya")],
+        );
+        assert_eq!(message, "`{data_type}` is not a valid sankhya");
+    }
+}
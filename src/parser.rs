@@ -1,326 +1,1731 @@
 // src/parser.rs
 
 use crate::ast::*;
-use crate::tokens::Token;
+use crate::checker;
+use crate::interpreter;
+use crate::span::Span;
+use crate::tokens::{TemplatePart, Token};
+
+use std::fmt;
+
+/// What the parser was looking for when it hit an unexpected token.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Token(Token),
+    Identifier,
+    Expression,
+    Statement,
+    /// The `}` closing an interpolation inside a string.
+    InterpolationEnd,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Token(token) => write!(f, "`{}`", token),
+            Expected::Identifier => write!(f, "a name"),
+            Expected::Expression => write!(f, "a value"),
+            Expected::Statement => write!(f, "a statement"),
+            Expected::InterpolationEnd => write!(f, "`}}`"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    UnexpectedToken {
+        expected: Expected,
+        found: Token,
+        span: Span,
+    },
+    /// A block opened at `opened` reached the end of the file without `aage`.
+    MissingBlockEnd { opened: Span, span: Span },
+    /// The parameter list of `function` is not closed with `fir`.
+    MissingParamEnd { function: String, span: Span },
+    /// An expression left `count` values that no operator combines.
+    MissingOperator { count: usize, span: Span },
+    /// `op` came with fewer than two values before it.
+    MissingOperand { op: BinOp, span: Span },
+    /// `name` is declared with `mano` or `banao` a second time in the same
+    /// block, or again in a body that already has it as a parameter, loop
+    /// variable or `pakdo` variable.
+    Redeclared {
+        name: String,
+        previous: Span,
+        span: Span,
+    },
+    /// `ruko` or `agla` used outside of any loop.
+    OutsideLoop { keyword: Token, span: Span },
+    /// `wapas karo` used outside of any function.
+    ReturnOutsideFunction { span: Span },
+    /// `badlo` of something that is neither a name nor a list element.
+    InvalidAssignTarget { span: Span },
+    /// A `kosh` literal with a key that has no value after it.
+    OddKoshItems { span: Span },
+    /// A `dhancha` that lists the field `name` twice.
+    DuplicateField {
+        name: String,
+        previous: Span,
+        span: Span,
+    },
+    /// A new value given to `name`, which was declared with `sthir` at
+    /// `constant`.
+    ConstantAssignment {
+        name: String,
+        constant: Span,
+        span: Span,
+    },
+    /// `name` declared again while the `sthir` at `constant` is in scope.
+    ConstantRedeclared {
+        name: String,
+        constant: Span,
+        span: Span,
+    },
+    /// A `jab` case that is not a literal, or a range that is not between
+    /// two numbers.
+    InvalidCase { span: Span },
+    /// A `milao` on booleans with no case for `missing` and no `warna`.
+    MissingCase { missing: Token, span: Span },
+}
+
+impl ParseError {
+    pub fn span(&self) -> Span {
+        match self {
+            ParseError::UnexpectedToken { span, .. }
+            | ParseError::MissingBlockEnd { span, .. }
+            | ParseError::MissingParamEnd { span, .. }
+            | ParseError::MissingOperator { span, .. }
+            | ParseError::MissingOperand { span, .. }
+            | ParseError::Redeclared { span, .. }
+            | ParseError::OutsideLoop { span, .. }
+            | ParseError::ReturnOutsideFunction { span }
+            | ParseError::InvalidAssignTarget { span }
+            | ParseError::OddKoshItems { span }
+            | ParseError::DuplicateField { span, .. }
+            | ParseError::ConstantAssignment { span, .. }
+            | ParseError::ConstantRedeclared { span, .. }
+            | ParseError::InvalidCase { span }
+            | ParseError::MissingCase { span, .. } => *span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let span = self.span();
+        write!(f, "{}:{}: ", span.line, span.column)?;
+        match self {
+            ParseError::UnexpectedToken {
+                expected: Expected::Statement,
+                found,
+                ..
+            } => write!(f, "`{}` cannot start a statement", found),
+            ParseError::UnexpectedToken {
+                expected,
+                found: Token::TheEnd,
+                ..
+            } => write!(f, "expected {}, found the end of the code", expected),
+            ParseError::UnexpectedToken {
+                expected, found, ..
+            } => write!(f, "expected {}, found `{}`", expected, found),
+            ParseError::MissingBlockEnd { opened, .. } => write!(
+                f,
+                "block opened at {}:{} is never closed with `aage`",
+                opened.line, opened.column
+            ),
+            ParseError::MissingParamEnd { function, .. } => {
+                write!(f, "expected `fir` after the parameters of `{}`", function)
+            }
+            ParseError::MissingOperator { count, .. } => {
+                write!(f, "{} values are left without an operator", count)
+            }
+            ParseError::MissingOperand { op, .. } => {
+                write!(f, "`{}` needs two values before it", op)
+            }
+            ParseError::Redeclared { name, previous, .. } => write!(
+                f,
+                "`{}` is already declared in this block at {}:{}",
+                name, previous.line, previous.column
+            ),
+            ParseError::OutsideLoop { keyword, .. } => {
+                write!(f, "`{}` can only be used inside a loop", keyword)
+            }
+            ParseError::ReturnOutsideFunction { .. } => {
+                write!(f, "`wapas karo` can only be used inside a function")
+            }
+            ParseError::InvalidAssignTarget { .. } => write!(f, "cannot assign to this expression"),
+            ParseError::OddKoshItems { .. } => write!(f, "a key in this kosh has no value"),
+            ParseError::DuplicateField { name, .. } => {
+                write!(f, "field `{}` is listed more than once", name)
+            }
+            ParseError::ConstantAssignment { name, .. } => {
+                write!(f, "cannot change the constant `{}`", name)
+            }
+            ParseError::ConstantRedeclared { name, .. } => {
+                write!(f, "`{}` is a constant and cannot be declared again", name)
+            }
+            ParseError::InvalidCase { .. } => write!(f, "a `jab` case must be a fixed value"),
+            ParseError::MissingCase { missing, .. } => {
+                write!(f, "`milao` does not handle `{}`", missing)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Code that parses but most likely does not do what was meant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseWarning {
+    /// A `jab` case that can never match, because the earlier case at
+    /// `previous` matches all of its values or, without one, because its
+    /// range is empty.
+    UnreachableCase { previous: Option<Span>, span: Span },
+    /// The `warna` of a `milao` whose cases already match every value.
+    UnreachableDefault { span: Span },
+}
+
+impl ParseWarning {
+    pub fn span(&self) -> Span {
+        match self {
+            ParseWarning::UnreachableCase { span, .. }
+            | ParseWarning::UnreachableDefault { span } => *span,
+        }
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let span = self.span();
+        write!(f, "{}:{}: ", span.line, span.column)?;
+        match self {
+            ParseWarning::UnreachableCase { .. } => write!(f, "this case can never match"),
+            ParseWarning::UnreachableDefault { .. } => write!(f, "this `warna` can never run"),
+        }
+    }
+}
 
 pub struct Parser<'a> {
-    lexer: &'a mut Vec<Token>,
+    tokens: &'a [(Token, Span)],
     current_token: usize,
+    errors: Vec<ParseError>,
+    warnings: Vec<ParseWarning>,
+    /// Names declared with `mano` or `sthir` in each enclosing block,
+    /// innermost last.
+    scopes: Vec<Vec<Binding>>,
+    /// How many loops enclose the current statement, within the current
+    /// function.
+    loop_depth: usize,
+    in_function: bool,
+}
+
+/// A name declared in a block.
+struct Binding {
+    name: String,
+    span: Span,
+    constant: bool,
+    /// The value of a `sthir` whose expression could be folded.
+    value: Option<ExpressionKind>,
 }
 
+/// A value or a logical operator among the items of a call or collection.
+enum Part {
+    Value(Expression),
+    Operator(Token, Span),
+}
+
+type Parts = std::iter::Peekable<std::vec::IntoIter<Part>>;
+
 impl<'a> Parser<'a> {
-    pub fn new(lexer: &'a mut Vec<Token>) -> Self {
+    pub fn new(tokens: &'a [(Token, Span)]) -> Self {
         Parser {
-            lexer,
+            tokens,
             current_token: 0,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            scopes: vec![Vec::new()],
+            loop_depth: 0,
+            in_function: false,
         }
     }
 
     fn next_token(&mut self) {
         if self.has_next() {
-            self.current_token = self.current_token + 1;
+            self.current_token += 1;
         }
     }
 
-    fn previous_token(&mut self) {
-        if self.current_token > 0 {
-            self.current_token = self.current_token - 1;
-        }
+    fn current_token(&self) -> Token {
+        self.tokens[self.current_token].0.clone()
     }
 
-    fn current_token(&self) -> Token {
-        self.lexer[self.current_token].clone()
+    fn current_span(&self) -> Span {
+        self.tokens[self.current_token].1
+    }
+
+    /// Span from `start` up to the end of the last consumed token.
+    fn span_from(&self, start: Span) -> Span {
+        let last = self.current_token.saturating_sub(1);
+        start.to(self.tokens[last].1)
     }
 
     fn has_next(&self) -> bool {
         self.current_token() != Token::TheEnd
     }
 
-    pub fn print_tokens(&mut self) {
+    fn unexpected(&self, expected: Expected) -> ParseError {
+        ParseError::UnexpectedToken {
+            expected,
+            found: self.current_token(),
+            span: self.current_span(),
+        }
+    }
 
-        let current_state = self.current_token;
-        self.current_token = 0;
+    /// Consumes `token` or reports what was found instead.
+    fn expect(&mut self, token: Token) -> Result<(), ParseError> {
+        if self.current_token() == token {
+            self.next_token();
+            Ok(())
+        } else {
+            Err(self.unexpected(Expected::Token(token)))
+        }
+    }
 
-        while self.has_next() {
-            // println!("{:?}", self.current_token());
+    fn expect_identifier(&mut self) -> Result<String, ParseError> {
+        if let Token::Identifier(name) = self.current_token() {
+            self.next_token();
+            Ok(name)
+        } else {
+            Err(self.unexpected(Expected::Identifier))
+        }
+    }
+
+    /// Skips ahead to the next token that can start or close a statement.
+    /// Always moves past at least one token when the failed statement did
+    /// not consume anything, so recovery cannot loop forever.
+    fn synchronize(&mut self, start: usize) {
+        if self.current_token == start {
             self.next_token();
         }
-        self.current_token = current_state;
+
+        while self.has_next() {
+            match self.current_token() {
+                Token::Let
+                | Token::Const
+                | Token::Update
+                | Token::If
+                | Token::While
+                | Token::Match
+                | Token::Case
+                | Token::Try
+                | Token::Catch
+                | Token::Throw
+                | Token::For
+                | Token::Break
+                | Token::Continue
+                | Token::Print
+                | Token::Input
+                | Token::Append
+                | Token::Remove
+                | Token::Struct
+                | Token::FunctionDef
+                | Token::FunctionCallStart
+                | Token::BlockEnd
+                | Token::Else
+                | Token::Return => break,
+                _ => self.next_token(),
+            }
+        }
     }
-    
-    pub fn parse(&mut self) -> Program {
+
+    pub fn parse(&mut self) -> Result<Program, Vec<ParseError>> {
         let mut statements = Vec::new();
-        
+
         while self.has_next() {
-            // println!("{:?}", self.current_token());
-            if let Some(statement) = self.parse_statement() {
-                // println!("{:?}", statement);
-                statements.push(statement);
+            let start = self.current_token;
+            match self.parse_statement() {
+                Ok(statement) => statements.push(statement),
+                Err(error) => {
+                    self.errors.push(error);
+                    self.synchronize(start);
+                }
             }
         }
 
-        Program { statements }
+        let program = Program { statements };
+        self.errors.extend(checker::check(&program));
+
+        if self.errors.is_empty() {
+            Ok(program)
+        } else {
+            Err(std::mem::take(&mut self.errors))
+        }
     }
 
-    fn parse_function_def(&mut self) -> Statement {
+    /// Warnings found by the last call to [`Parser::parse`].
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    fn parse_function_def(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
         self.next_token(); // banao
-        // println!("{:?}", self.current_token());
-        let func_name = if let Token::Identifier(name) = self.current_token() {
-            name.clone()
-        } else {
-            panic!("Expected identifier after 'banao'")
-        };
 
-        self.next_token();
-        // println!("{:?}", self.current_token());
+        let name_span = self.current_span();
+        let func_name = self.expect_identifier()?;
+        self.declare(&func_name, name_span, false, None);
+
+        let (params, body) = self.parse_function_rest(&func_name, start)?;
+
+        Ok(Statement::new(
+            StatementKind::FunctionDef(func_name, params, body),
+            self.span_from(start),
+        ))
+    }
 
-        self.next_token(); // jo le
-        // println!("{:?}", self.current_token());
+    /// Parses an anonymous `banao jo le <params> fir <body> aage`.
+    fn parse_function_expression(&mut self) -> Result<Expression, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // banao
+
+        let (params, body) = self.parse_function_rest("banao", start)?;
+
+        Ok(Expression::new(
+            ExpressionKind::Function(params, body),
+            self.span_from(start),
+        ))
+    }
+
+    /// Parses the `jo le <params> fir <body> aage` shared by named and
+    /// anonymous functions.
+    fn parse_function_rest(
+        &mut self,
+        func_name: &str,
+        start: Span,
+    ) -> Result<(Vec<String>, Vec<Statement>), ParseError> {
+        self.expect(Token::ParamStart)?; // jo le
 
         let mut params = Vec::new();
         while self.current_token() != Token::ParamEnd {
-            params.push(self.parse_primary());
-            self.next_token(); 
-            // println!("{:?}", self.current_token());
+            match self.current_token() {
+                Token::Identifier(name) => {
+                    params.push((name, self.current_span()));
+                    self.next_token();
+                }
+                _ => {
+                    return Err(ParseError::MissingParamEnd {
+                        function: func_name.to_string(),
+                        span: self.current_span(),
+                    })
+                }
+            }
         }
 
         self.next_token(); // fir
-        // println!("{:?}", self.current_token());
-        
-        let body = self.parse_block();
 
+        let loop_depth = std::mem::take(&mut self.loop_depth);
+        let in_function = std::mem::replace(&mut self.in_function, true);
+        let body = self.parse_block_with(params.clone());
+        self.loop_depth = loop_depth;
+        self.in_function = in_function;
+
+        self.expect_block_end(start)?;
+
+        Ok((params.into_iter().map(|(name, _)| name).collect(), body))
+    }
+
+    /// Parses `wapas karo`, with the returned value if one follows.
+    fn parse_return(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
         self.next_token(); // wapas karo
-        // println!("{:?}", self.current_token());
 
-        let return_data = self.parse_expression();
+        if !self.in_function {
+            return Err(ParseError::ReturnOutsideFunction { span: start });
+        }
 
-        Statement::FunctionDef(func_name, params, body, return_data)
+        let value = if self.at_primary() || self.current_token() == Token::Not {
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+
+        Ok(Statement::new(
+            StatementKind::Return(value),
+            self.span_from(start),
+        ))
     }
 
-    fn parse_function_call(&mut self) -> Statement {
+    /// Parses a call statement: `chalao f a b par d me` stores the result
+    /// in `d`, while a call without `d me` discards it.
+    fn parse_function_call(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
 
-        self.next_token(); // chalao
-        // println!("{:?}", self.current_token());
-        
-        let func_name = if let Token::Identifier(name) = self.current_token() {
-            name.clone()
+        let (func_name, args) = self.parse_call()?;
+
+        let kind = if let Token::Identifier(var_name) = self.current_token() {
+            self.next_token();
+            self.expect(Token::FunctionCallEnd)?; // me
+            StatementKind::FunctionCall(func_name, args, var_name)
         } else {
-            panic!("Expected identifier after 'mana'")
+            let call = ExpressionKind::Call(func_name, args);
+            StatementKind::Expression(Expression::new(call, self.span_from(start)))
+        };
+
+        Ok(Statement::new(kind, self.span_from(start)))
+    }
+
+    /// Parses `chalao <name> <args> par`, where the arguments are read by
+    /// [`Parser::parse_items`].
+    fn parse_call(&mut self) -> Result<(String, Vec<Expression>), ParseError> {
+        self.next_token(); // chalao
+
+        let func_name = self.expect_identifier()?;
+
+        let args = self
+            .parse_items()?
+            .into_iter()
+            .map(|arg| self.fold(arg))
+            .collect();
+
+        self.expect(Token::FunctionReturn)?; // par
+
+        Ok((func_name, args))
+    }
+
+    fn parse_statement(&mut self) -> Result<Statement, ParseError> {
+        match self.current_token() {
+            Token::Let | Token::Const => self.parse_declaration(),
+            Token::Update => self.parse_assignment(),
+            Token::If => self.parse_if(),
+            Token::While => self.parse_while(),
+            Token::Match => self.parse_match(),
+            Token::Try => self.parse_try(),
+            Token::Throw => self.parse_throw(),
+            Token::For => self.parse_for(),
+            Token::Break | Token::Continue => self.parse_loop_control(),
+            Token::Return => self.parse_return(),
+            Token::Append => self.parse_append(),
+            Token::Remove => self.parse_remove(),
+            Token::Struct => self.parse_struct_def(),
+            Token::Print => self.parse_print(),
+            Token::Input => self.parse_input(),
+            Token::FunctionDef => self.parse_function_def(),
+            Token::FunctionCallStart => self.parse_function_call(),
+            _ => Err(self.unexpected(Expected::Statement)),
+        }
+    }
+
+    /// Parses `mano x barabar ...`, or `sthir x barabar ...` for a constant.
+    fn parse_declaration(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        let constant = self.current_token() == Token::Const;
+        self.next_token(); // mano / sthir
+
+        let name_span = self.current_span();
+        let var_name = self.expect_identifier()?;
+
+        self.expect(Token::Assign)?; // barabar
+
+        let expr = self.parse_expression()?;
+
+        // Declared only after the value is parsed, so the value can still
+        // use an outer variable of the same name.
+        let value = match &expr.kind {
+            ExpressionKind::Integer(_)
+            | ExpressionKind::Float(_)
+            | ExpressionKind::String(_)
+            | ExpressionKind::Boolean(_)
+                if constant =>
+            {
+                Some(expr.kind.clone())
+            }
+            _ => None,
         };
-        self.next_token(); // func_name
-        // println!("{:?}", self.current_token());
-        
-        let mut args: Vec<Expression> = Vec::new();
-        while self.current_token() != Token::FunctionReturn {
-            args.push(self.parse_primary());
-            self.next_token(); 
-            // println!("{:?}", self.current_token());
-        }
-
-        self.next_token(); // par
-        // println!("{:?}", self.current_token());
-        
-        let var_name = if let Token::Identifier(name) = self.current_token() {
-            name.clone()
+        self.declare(&var_name, name_span, constant, value);
+
+        let kind = if constant {
+            StatementKind::Constant(var_name, expr)
         } else {
-            panic!("Expected identifier after 'mana'")
+            StatementKind::Declaration(var_name, expr)
         };
-        self.next_token(); // return_var
-        // println!("{:?}", self.current_token());
+        Ok(Statement::new(kind, self.span_from(start)))
+    }
 
-        if self.current_token() != Token::FunctionCallEnd {
-            panic!("Invalid Syntax")
+    /// Records a declaration of `name` by `mano`, `sthir` or `banao`,
+    /// reporting it if the current block already declares it. Declaring a
+    /// constant again is left to the checker, which reports it as such.
+    fn declare(&mut self, name: &str, span: Span, constant: bool, value: Option<ExpressionKind>) {
+        let scope = self.scopes.last_mut().unwrap();
+        if let Some(previous) = scope.iter().find(|binding| binding.name == name) {
+            if previous.constant {
+                return;
+            }
+            self.errors.push(ParseError::Redeclared {
+                name: name.to_string(),
+                previous: previous.span,
+                span,
+            });
         } else {
-            self.next_token(); //  me
+            scope.push(Binding {
+                name: name.to_string(),
+                span,
+                constant,
+                value,
+            });
         }
+    }
 
-        Statement::FunctionCall(func_name, args, var_name)
+    /// The binding that `name` currently refers to, if the parser has seen
+    /// its declaration.
+    fn binding(&self, name: &str) -> Option<&Binding> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.iter().find(|binding| binding.name == name))
     }
 
-    fn parse_statement(&mut self) -> Option<Statement> {
-        let statement = match self.current_token() {
-            Token::Let => Some(self.parse_declaration()),
-            Token::If => Some(self.parse_if()),
-            Token::While => Some(self.parse_while()),
-            Token::Print => Some(self.parse_print()),
-            Token::Input => Some(self.parse_input()),
-            Token::FunctionDef => Some(self.parse_function_def()),
-            Token::FunctionCallStart => Some(self.parse_function_call()),
-            _ => None,
+    fn parse_assignment(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // badlo
+
+        let target = self.parse_operation()?;
+
+        self.expect(Token::Assign)?; // barabar
+
+        let expr = self.parse_expression()?;
+
+        let kind = match target.kind {
+            ExpressionKind::Identifier(var_name) => StatementKind::Assignment(var_name, expr),
+            ExpressionKind::BinaryOp(list, BinOp::Index, index) => StatementKind::IndexAssignment {
+                list: *list,
+                index: *index,
+                value: expr,
+            },
+            ExpressionKind::Field(record, field) => StatementKind::FieldAssignment {
+                record: *record,
+                field,
+                value: expr,
+            },
+            _ => return Err(ParseError::InvalidAssignTarget { span: target.span }),
         };
-        statement
+
+        Ok(Statement::new(kind, self.span_from(start)))
     }
 
-    fn parse_declaration(&mut self) -> Statement {
-        self.next_token();
-        // println!("{:?}", self.current_token());
+    fn parse_struct_def(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // dhancha
 
-        let var_name = if let Token::Identifier(name) = self.current_token() {
-            name.clone()
-        } else {
-            panic!("Expected identifier after 'mana'")
-        };
-        self.next_token();
-        // println!("{:?}", self.current_token());
+        let name = self.expect_identifier()?;
 
-        if self.current_token() != Token::Assign {
-            panic!("Invalid syntax")
+        let mut fields: Vec<(String, Span)> = Vec::new();
+        while let Token::Identifier(field) = self.current_token() {
+            let span = self.current_span();
+            self.next_token();
+            if let Some((_, previous)) = fields.iter().find(|(seen, _)| *seen == field) {
+                self.errors.push(ParseError::DuplicateField {
+                    name: field,
+                    previous: *previous,
+                    span,
+                });
+            } else {
+                fields.push((field, span));
+            }
         }
 
-        self.next_token();
-        // println!("{:?}", self.current_token());
+        self.expect_block_end(start)?;
+
+        let fields = fields.into_iter().map(|(field, _)| field).collect();
+        Ok(Statement::new(
+            StatementKind::StructDef(name, fields),
+            self.span_from(start),
+        ))
+    }
 
-        let expr = self.parse_expression();
+    fn parse_append(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // jodo
 
-        Statement::Declaration(var_name, expr)
+        let list = self.parse_expression()?;
+        self.expect(Token::FunctionCallEnd)?; // me
+        let value = self.parse_expression()?;
+
+        Ok(Statement::new(
+            StatementKind::Append { list, value },
+            self.span_from(start),
+        ))
     }
 
-    fn parse_if(&mut self) -> Statement {
-        self.next_token();
-        // println!("{:?}", self.current_token());
+    fn parse_remove(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // hatao
+
+        let list = self.parse_expression()?;
+        self.expect(Token::From)?; // se
+        let index = self.parse_expression()?;
+
+        Ok(Statement::new(
+            StatementKind::Remove { list, index },
+            self.span_from(start),
+        ))
+    }
 
-        let condition = self.parse_expression();
+    fn parse_if(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+
+        let mut statement = self.parse_if_branches()?;
+
+        self.expect_block_end(start)?;
+
+        statement.span = self.span_from(start);
+        Ok(statement)
+    }
+
+    /// Parses `agar <condition> <block>` and any `warna agar` or `warna`
+    /// branches after it, up to the single `aage` that closes the chain.
+    /// Each `warna agar` becomes an `If` nested in the else block.
+    fn parse_if_branches(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // agar
+
+        let condition = self.parse_expression()?;
 
         let then_block = self.parse_block();
 
         let else_block = if let Token::Else = self.current_token() {
-            self.next_token();
-            // println!("{:?}", self.current_token());
+            self.next_token(); // warna
+            if let Token::If = self.current_token() {
+                vec![self.parse_if_branches()?]
+            } else {
+                self.parse_block()
+            }
+        } else {
+            Vec::new()
+        };
 
+        Ok(Statement::new(
+            StatementKind::If {
+                condition,
+                then_block,
+                else_block,
+            },
+            self.span_from(start),
+        ))
+    }
+
+    /// Parses `milao <value>`, its `jab` cases and an optional `warna`, all
+    /// closed by a single `aage`. Cases that can never match are warned
+    /// about, and a `milao` on booleans must handle both of them.
+    fn parse_match(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // milao
+
+        let value = self.parse_expression()?;
+
+        let mut cases: Vec<(Case, Vec<Statement>)> = Vec::new();
+        let mut case_spans = Vec::new();
+        while self.current_token() == Token::Case {
+            let case_start = self.current_span();
+            self.next_token(); // jab
+
+            let first = self.parse_expression()?;
+            let case = if self.current_token() == Token::From {
+                self.next_token(); // se
+                let end = self.parse_expression()?;
+                self.expect(Token::To)?; // tak
+                Case::Range(first, end)
+            } else {
+                Case::Value(first)
+            };
+            let case_span = self.span_from(case_start);
+            self.check_case(&case, case_span, &cases, &case_spans);
+
+            self.expect(Token::ParamEnd)?; // fir
+
+            let body = self.parse_block();
+            cases.push((case, body));
+            case_spans.push(case_span);
+        }
+
+        if cases.is_empty() {
+            return Err(self.unexpected(Expected::Token(Token::Case)));
+        }
+
+        let booleans: Vec<bool> = cases
+            .iter()
+            .filter_map(|(case, _)| match case {
+                Case::Value(Expression {
+                    kind: ExpressionKind::Boolean(b),
+                    ..
+                }) => Some(*b),
+                _ => None,
+            })
+            .collect();
+        let missing = [true, false]
+            .into_iter()
+            .find(|b| booleans.len() == cases.len() && !booleans.contains(b));
+
+        let default = if let Token::Else = self.current_token() {
+            if booleans.len() == cases.len() && missing.is_none() {
+                let span = self.current_span();
+                self.warnings
+                    .push(ParseWarning::UnreachableDefault { span });
+            }
+            self.next_token(); // warna
             self.parse_block()
         } else {
+            if let Some(missing) = missing {
+                self.errors.push(ParseError::MissingCase {
+                    missing: if missing { Token::True } else { Token::False },
+                    span: start,
+                });
+            }
             Vec::new()
         };
-        Statement::If {
-            condition,
-            then_block,
-            else_block,
+
+        self.expect_block_end(start)?;
+
+        Ok(Statement::new(
+            StatementKind::Match {
+                value,
+                cases,
+                default,
+            },
+            self.span_from(start),
+        ))
+    }
+
+    /// Reports a `jab` case that is not made of literals, and warns about
+    /// one that no value can reach.
+    fn check_case(
+        &mut self,
+        case: &Case,
+        span: Span,
+        earlier: &[(Case, Vec<Statement>)],
+        spans: &[Span],
+    ) {
+        let valid = match case {
+            Case::Value(value) => matches!(
+                value.kind,
+                ExpressionKind::Integer(_)
+                    | ExpressionKind::Float(_)
+                    | ExpressionKind::String(_)
+                    | ExpressionKind::Boolean(_)
+            ),
+            Case::Range(start, end) => number(start).is_some() && number(end).is_some(),
+        };
+        if !valid {
+            self.errors.push(ParseError::InvalidCase { span });
+            return;
+        }
+
+        if let Case::Range(start, end) = case {
+            if number(start) > number(end) {
+                self.warnings.push(ParseWarning::UnreachableCase {
+                    previous: None,
+                    span,
+                });
+                return;
+            }
+        }
+        if let Some(previous) = earlier
+            .iter()
+            .zip(spans)
+            .find(|((earlier, _), _)| covers(earlier, case))
+            .map(|(_, previous)| *previous)
+        {
+            self.warnings.push(ParseWarning::UnreachableCase {
+                previous: Some(previous),
+                span,
+            });
         }
     }
 
-    fn parse_while(&mut self) -> Statement {
-        self.next_token();
-        // println!("{:?}", self.current_token());
+    /// Parses `koshish <body> pakdo <name> <handler> aage`. The handler runs
+    /// with the error in `name` when the body fails or uses `fenko`.
+    fn parse_try(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // koshish
 
-        let condition = self.parse_expression();
         let body = self.parse_block();
-        Statement::While { condition, body }
+
+        self.expect(Token::Catch)?; // pakdo
+        let variable_span = self.current_span();
+        let variable = self.expect_identifier()?;
+
+        let handler = self.parse_block_with(vec![(variable.clone(), variable_span)]);
+
+        self.expect_block_end(start)?;
+
+        Ok(Statement::new(
+            StatementKind::Try {
+                body,
+                variable,
+                handler,
+            },
+            self.span_from(start),
+        ))
     }
 
-    fn parse_print(&mut self) -> Statement {
-        // println!("{:?}", self.current_token());
-        self.next_token();
-        // println!("{:?}", self.current_token());
+    fn parse_throw(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // fenko
 
-        let expr = self.parse_expression();
+        let value = self.parse_expression()?;
 
-        Statement::Print(expr)
+        Ok(Statement::new(
+            StatementKind::Throw(value),
+            self.span_from(start),
+        ))
     }
 
-    fn parse_input(&mut self) -> Statement {
-        self.next_token();
-        // println!("{:?}", self.current_token());
+    fn parse_while(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // jabtak
+
+        let condition = self.parse_expression()?;
+        let body = self.parse_loop_body(Vec::new());
+
+        self.expect_block_end(start)?;
 
-        let var_name = if let Token::Identifier(name) = self.current_token() {
-            name.clone()
+        Ok(Statement::new(
+            StatementKind::While { condition, body },
+            self.span_from(start),
+        ))
+    }
+
+    fn parse_for(&mut self) -> Result<Statement, ParseError> {
+        let start_span = self.current_span();
+        self.next_token(); // har
+
+        let variable_span = self.current_span();
+        let variable = self.expect_identifier()?;
+        let names = vec![(variable.clone(), variable_span)];
+
+        let start = self.parse_expression()?;
+
+        if let Token::FunctionCallEnd = self.current_token() {
+            self.next_token(); // me
+            let body = self.parse_loop_body(names);
+            self.expect_block_end(start_span)?;
+            return Ok(Statement::new(
+                StatementKind::ForEach {
+                    variable,
+                    iterable: start,
+                    body,
+                },
+                self.span_from(start_span),
+            ));
+        }
+
+        self.expect(Token::From)?; // se
+        let end = self.parse_expression()?;
+        self.expect(Token::To)?; // tak
+
+        let step = if let Token::Step = self.current_token() {
+            self.next_token(); // kadam
+            Some(self.parse_expression()?)
         } else {
-            panic!("Expected identifier after 'padho'")
+            None
         };
-        self.next_token();
-        // println!("{:?}", self.current_token());
-        let var_type = if let Token::Identifier(name) = self.current_token() {
-            name.clone()
-        } else {
-            panic!("Expected identifier after 'padho'")
+
+        let body = self.parse_loop_body(names);
+
+        self.expect_block_end(start_span)?;
+
+        Ok(Statement::new(
+            StatementKind::For {
+                variable,
+                start,
+                end,
+                step,
+                body,
+            },
+            self.span_from(start_span),
+        ))
+    }
+
+    fn parse_loop_body(&mut self, names: Vec<(String, Span)>) -> Vec<Statement> {
+        self.loop_depth += 1;
+        let body = self.parse_block_with(names);
+        self.loop_depth -= 1;
+        body
+    }
+
+    fn parse_loop_control(&mut self) -> Result<Statement, ParseError> {
+        let keyword = self.current_token();
+        let span = self.current_span();
+        self.next_token(); // ruko / agla
+
+        if self.loop_depth == 0 {
+            return Err(ParseError::OutsideLoop { keyword, span });
+        }
+
+        let kind = match keyword {
+            Token::Break => StatementKind::Break,
+            _ => StatementKind::Continue,
         };
+        Ok(Statement::new(kind, span))
+    }
 
-        self.next_token();
-        // println!("{:?}", self.current_token());
+    fn parse_print(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // likho
 
-        Statement::Input(var_name, var_type)
+        let expr = self.parse_expression()?;
+
+        Ok(Statement::new(
+            StatementKind::Print(expr),
+            self.span_from(start),
+        ))
     }
 
-    fn parse_expression(&mut self) -> Expression {
-        // This is a simplified version, only handles binary operations and literals for now
+    fn parse_input(&mut self) -> Result<Statement, ParseError> {
+        let start = self.current_span();
+        self.next_token(); // padho
 
-        let left = self.parse_primary();
-        self.next_token();
-        // println!("{:?}", self.current_token());
+        let data_type = self.expect_identifier()?;
+        let var_name = self.expect_identifier()?;
 
-        let right = self.parse_primary();
-        self.next_token();
-        // println!("{:?}", self.current_token());
-        
-        match right {
-            Expression::None => {
-                if self.current_token() != Token::TheEnd {
-                    // println!("{:?}", self.current_token());
-                    self.previous_token();
+        Ok(Statement::new(
+            StatementKind::Input(data_type, var_name),
+            self.span_from(start),
+        ))
+    }
+
+    /// Parses a full expression. `ya` binds looser than `aur`, which binds
+    /// looser than a leading `nhi`; both sides of `aur` and `ya` are postfix
+    /// operations, so `x 5 se bada hai aur y 0 hai` needs no parentheses.
+    /// Parts that can be worked out ahead of time are folded.
+    fn parse_expression(&mut self) -> Result<Expression, ParseError> {
+        let expr = self.parse_or()?;
+        Ok(self.fold(expr))
+    }
+
+    fn parse_or(&mut self) -> Result<Expression, ParseError> {
+        let mut left = self.parse_and()?;
+        while self.current_token() == Token::Or {
+            self.next_token(); // ya
+            let right = self.parse_and()?;
+            let span = left.span.to(right.span);
+            left = Expression::new(
+                ExpressionKind::BinaryOp(Box::new(left), BinOp::Or, Box::new(right)),
+                span,
+            );
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expression, ParseError> {
+        let mut left = self.parse_not()?;
+        while self.current_token() == Token::And {
+            self.next_token(); // aur
+            let right = self.parse_not()?;
+            let span = left.span.to(right.span);
+            left = Expression::new(
+                ExpressionKind::BinaryOp(Box::new(left), BinOp::And, Box::new(right)),
+                span,
+            );
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expression, ParseError> {
+        if self.current_token() != Token::Not {
+            return self.parse_operation();
+        }
+        let start = self.current_span();
+        self.next_token(); // nhi
+        let operand = self.parse_not()?;
+        Ok(Expression::new(
+            ExpressionKind::Not(Box::new(operand)),
+            self.span_from(start),
+        ))
+    }
+
+    /// Parses a postfix operation that must produce exactly one value.
+    fn parse_operation(&mut self) -> Result<Expression, ParseError> {
+        let mut operands = self.parse_operands(true)?;
+        match operands.len() {
+            0 => Err(self.unexpected(Expected::Expression)),
+            1 => Ok(operands.pop().unwrap()),
+            count => Err(ParseError::MissingOperator {
+                count,
+                span: operands[0].span.to(operands[count - 1].span),
+            }),
+        }
+    }
+
+    /// Parses a run of operands and postfix operators. Each operator
+    /// combines the two values before it, so `a b ka yog c ka guna` is
+    /// `(a + b) * c` and `a b c ka yog ka guna` is `a * (b + c)`; chains
+    /// therefore associate to the left. Parentheses group a sub-expression.
+    /// Returns the values left over, in order.
+    ///
+    /// With `single`, a `chalao` after the first value that no operator ends
+    /// up consuming is left unparsed, since it starts the next statement:
+    /// `likho x chalao f par` is two statements.
+    fn parse_operands(&mut self, single: bool) -> Result<Vec<Expression>, ParseError> {
+        let mut stack: Vec<Expression> = Vec::new();
+        // Stack height, token and error count before a call that may turn
+        // out to be the next statement.
+        let mut pending_call: Option<(usize, usize, usize)> = None;
+        loop {
+            if self.at_primary() {
+                if single
+                    && pending_call.is_none()
+                    && !stack.is_empty()
+                    && self.current_token() == Token::FunctionCallStart
+                {
+                    pending_call = Some((stack.len(), self.current_token, self.errors.len()));
+                }
+                stack.push(self.parse_primary()?);
+            } else if let Some(op) = binary_operator(&self.current_token()) {
+                let op_span = self.current_span();
+                if stack.len() < 2 {
+                    return Err(ParseError::MissingOperand { op, span: op_span });
+                }
+                self.next_token();
+                let right = stack.pop().unwrap();
+                let left = stack.pop().unwrap();
+                let span = left.span.to(op_span);
+                stack.push(Expression::new(
+                    ExpressionKind::BinaryOp(Box::new(left), op, Box::new(right)),
+                    span,
+                ));
+                if matches!(pending_call, Some((height, _, _)) if stack.len() <= height) {
+                    pending_call = None;
                 }
-                left
+            } else if self.current_token() == Token::Length {
+                let Some(value) = stack.pop() else {
+                    return Err(self.unexpected(Expected::Expression));
+                };
+                let span = value.span.to(self.current_span());
+                self.next_token(); // ki lambai
+                stack.push(Expression::new(
+                    ExpressionKind::Length(Box::new(value)),
+                    span,
+                ));
+            } else if self.current_token() == Token::Of {
+                let Some(record) = stack.pop() else {
+                    return Err(self.unexpected(Expected::Expression));
+                };
+                self.next_token(); // ka
+                let field_span = self.current_span();
+                let field = self.expect_identifier()?;
+                let span = record.span.to(field_span);
+                stack.push(Expression::new(
+                    ExpressionKind::Field(Box::new(record), field),
+                    span,
+                ));
+            } else {
+                if let Some((height, token, errors)) = pending_call {
+                    stack.truncate(height);
+                    self.current_token = token;
+                    self.errors.truncate(errors);
+                }
+                return Ok(stack);
             }
-            _ => {
-                if self.has_next() {
-                    let op = self.parse_binary_operator().unwrap();
+        }
+    }
+
+    /// Parses the values of a call, `suchi`, `kosh` or `naya`. These are
+    /// runs of postfix operations, where `aur`, `ya` and `nhi` work on the
+    /// single values next to them just as in a condition: `a b aur c` is the
+    /// two items `a` and `b aur c`.
+    fn parse_items(&mut self) -> Result<Vec<Expression>, ParseError> {
+        let mut parts = Vec::new();
+        loop {
+            match self.current_token() {
+                token @ (Token::Not | Token::And | Token::Or) => {
+                    parts.push(Part::Operator(token, self.current_span()));
                     self.next_token();
-                    // println!("{:?}", self.current_token());
-                    Expression::BinaryOp(Box::new(left), op, Box::new(right))
-                } else {
-                    left
                 }
+                _ if self.at_primary() => {
+                    let values = self.parse_operands(false)?;
+                    parts.extend(values.into_iter().map(Part::Value));
+                }
+                _ => break,
             }
         }
+
+        let mut parts = parts.into_iter().peekable();
+        let mut items = Vec::new();
+        while parts.peek().is_some() {
+            items.push(self.combine_or(&mut parts)?);
+        }
+        Ok(items)
     }
 
-    fn parse_primary(&mut self) -> Expression {
-        match self.current_token() {
-            Token::Integer(value) => Expression::Integer(value),
-            Token::Float(value) => Expression::Float(value),
-            Token::String(value) => Expression::String(value),
-            Token::Identifier(name) => Expression::Identifier(name),
-            Token::True => Expression::Boolean(true),
-            Token::False => Expression::Boolean(false),
-            _ => Expression::None,
+    fn combine_or(&self, parts: &mut Parts) -> Result<Expression, ParseError> {
+        let mut left = self.combine_and(parts)?;
+        while let Some(Part::Operator(Token::Or, _)) = parts.peek() {
+            parts.next(); // ya
+            let right = self.combine_and(parts)?;
+            let span = left.span.to(right.span);
+            left = Expression::new(
+                ExpressionKind::BinaryOp(Box::new(left), BinOp::Or, Box::new(right)),
+                span,
+            );
         }
+        Ok(left)
     }
 
-    fn parse_binary_operator(&mut self) -> Option<BinOp> {
-        match self.current_token() {
-            Token::Plus => Some(BinOp::Plus),
-            Token::Minus => Some(BinOp::Minus),
-            Token::Multiply => Some(BinOp::Multiply),
-            Token::Divide => Some(BinOp::Divide),
-            Token::Modulo => Some(BinOp::Modulo),
-            Token::LessThan => Some(BinOp::LessThan),
-            Token::GreaterThan => Some(BinOp::GreaterThan),
-            Token::LessThanOrEqual => Some(BinOp::LessThanOrEqual),
-            Token::GreaterThanOrEqual => Some(BinOp::GreaterThanOrEqual),
-            Token::Equals => Some(BinOp::Equals),
-            Token::NotEquals => Some(BinOp::NotEquals),
-            _ => None,
+    fn combine_and(&self, parts: &mut Parts) -> Result<Expression, ParseError> {
+        let mut left = self.combine_not(parts)?;
+        while let Some(Part::Operator(Token::And, _)) = parts.peek() {
+            parts.next(); // aur
+            let right = self.combine_not(parts)?;
+            let span = left.span.to(right.span);
+            left = Expression::new(
+                ExpressionKind::BinaryOp(Box::new(left), BinOp::And, Box::new(right)),
+                span,
+            );
+        }
+        Ok(left)
+    }
+
+    fn combine_not(&self, parts: &mut Parts) -> Result<Expression, ParseError> {
+        match parts.next() {
+            Some(Part::Value(value)) => Ok(value),
+            Some(Part::Operator(Token::Not, start)) => {
+                let operand = self.combine_not(parts)?;
+                let span = start.to(operand.span);
+                Ok(Expression::new(
+                    ExpressionKind::Not(Box::new(operand)),
+                    span,
+                ))
+            }
+            Some(Part::Operator(found, span)) => Err(ParseError::UnexpectedToken {
+                expected: Expected::Expression,
+                found,
+                span,
+            }),
+            None => Err(self.unexpected(Expected::Expression)),
+        }
+    }
+
+    fn at_primary(&self) -> bool {
+        // `banao` starts a value only when it has no name, so that a named
+        // definition after an expression begins the next statement.
+        if self.current_token() == Token::FunctionDef {
+            return matches!(
+                self.tokens.get(self.current_token + 1),
+                Some((Token::ParamStart, _))
+            );
+        }
+
+        matches!(
+            self.current_token(),
+            Token::Integer(_)
+                | Token::Float(_)
+                | Token::String(_)
+                | Token::Template(_)
+                | Token::Identifier(_)
+                | Token::True
+                | Token::False
+                | Token::OpenParen
+                | Token::FunctionCallStart
+                | Token::List
+                | Token::Kosh
+                | Token::New
+        )
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, ParseError> {
+        if self.current_token() == Token::OpenParen {
+            let start = self.current_span();
+            self.next_token(); // (
+            let expr = self.parse_expression()?;
+            self.expect(Token::CloseParen)?; // )
+            return Ok(Expression::new(expr.kind, self.span_from(start)));
+        }
+
+        if self.current_token() == Token::List {
+            let start = self.current_span();
+            self.next_token(); // suchi
+            let items = self.parse_items()?;
+            self.expect(Token::BlockEnd)?; // aage
+            return Ok(Expression::new(
+                ExpressionKind::List(items),
+                self.span_from(start),
+            ));
+        }
+
+        if self.current_token() == Token::FunctionDef {
+            return self.parse_function_expression();
+        }
+
+        if self.current_token() == Token::New {
+            let start = self.current_span();
+            self.next_token(); // naya
+            let name = self.expect_identifier()?;
+            let values = self.parse_items()?;
+            self.expect(Token::BlockEnd)?; // aage
+            return Ok(Expression::new(
+                ExpressionKind::Record(name, values),
+                self.span_from(start),
+            ));
+        }
+
+        if self.current_token() == Token::Kosh {
+            let start = self.current_span();
+            self.next_token(); // kosh
+            let mut items = self.parse_items()?;
+            self.expect(Token::BlockEnd)?; // aage
+            if items.len() % 2 == 1 {
+                return Err(ParseError::OddKoshItems {
+                    span: items.pop().unwrap().span,
+                });
+            }
+            let mut entries = Vec::new();
+            let mut items = items.into_iter();
+            while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                entries.push((key, value));
+            }
+            return Ok(Expression::new(
+                ExpressionKind::Kosh(entries),
+                self.span_from(start),
+            ));
+        }
+
+        if self.current_token() == Token::FunctionCallStart {
+            let start = self.current_span();
+            let (func_name, args) = self.parse_call()?;
+            return Ok(Expression::new(
+                ExpressionKind::Call(func_name, args),
+                self.span_from(start),
+            ));
+        }
+
+        let kind = match self.current_token() {
+            Token::Integer(value) => ExpressionKind::Integer(value),
+            Token::Float(value) => ExpressionKind::Float(value),
+            Token::String(value) => ExpressionKind::String(value),
+            Token::Template(parts) => self.parse_template(parts)?,
+            Token::Identifier(name) => ExpressionKind::Identifier(name),
+            Token::True => ExpressionKind::Boolean(true),
+            Token::False => ExpressionKind::Boolean(false),
+            _ => return Err(self.unexpected(Expected::Expression)),
+        };
+        let span = self.current_span();
+        self.next_token();
+        Ok(Expression::new(kind, span))
+    }
+
+    /// Replaces each `sthir` with a known value by that value, then each
+    /// operation on literals alone by its result, from the inside out:
+    /// with `sthir PI barabar 3.14`, `PI 2 ka guna` becomes `6.28`.
+    fn fold(&self, expr: Expression) -> Expression {
+        let fold_all = |items: Vec<Expression>| -> Vec<Expression> {
+            items.into_iter().map(|item| self.fold(item)).collect()
+        };
+        let kind = match expr.kind {
+            ExpressionKind::Identifier(name) => match self.binding(&name) {
+                Some(Binding {
+                    value: Some(value), ..
+                }) => value.clone(),
+                _ => ExpressionKind::Identifier(name),
+            },
+            ExpressionKind::BinaryOp(lhs, op, rhs) => {
+                ExpressionKind::BinaryOp(Box::new(self.fold(*lhs)), op, Box::new(self.fold(*rhs)))
+            }
+            ExpressionKind::Not(operand) => ExpressionKind::Not(Box::new(self.fold(*operand))),
+            ExpressionKind::Call(name, args) => ExpressionKind::Call(name, fold_all(args)),
+            ExpressionKind::List(items) => ExpressionKind::List(fold_all(items)),
+            ExpressionKind::Kosh(items) => ExpressionKind::Kosh(
+                items
+                    .into_iter()
+                    .map(|(key, value)| (self.fold(key), self.fold(value)))
+                    .collect(),
+            ),
+            ExpressionKind::Record(name, values) => ExpressionKind::Record(name, fold_all(values)),
+            ExpressionKind::Field(record, field) => {
+                ExpressionKind::Field(Box::new(self.fold(*record)), field)
+            }
+            ExpressionKind::Length(value) => ExpressionKind::Length(Box::new(self.fold(*value))),
+            ExpressionKind::Interpolated(parts) => ExpressionKind::Interpolated(
+                parts
+                    .into_iter()
+                    .map(|part| match part {
+                        StringPart::Expression(part) => StringPart::Expression(self.fold(part)),
+                        text => text,
+                    })
+                    .collect(),
+            ),
+            kind => kind,
+        };
+        let expr = Expression::new(kind, expr.span);
+        match interpreter::fold(&expr) {
+            Some(value) => Expression::new(value, expr.span),
+            None => expr,
         }
     }
 
+    /// Parses the code inside each `{...}` of an interpolated string.
+    fn parse_template(&mut self, parts: Vec<TemplatePart>) -> Result<ExpressionKind, ParseError> {
+        let mut result = Vec::new();
+        for part in parts {
+            match part {
+                TemplatePart::Text(text) => result.push(StringPart::Text(text)),
+                TemplatePart::Code(tokens) => {
+                    let mut parser = Parser::new(&tokens);
+                    let expr = parser.parse_expression();
+                    self.errors.append(&mut parser.errors);
+                    self.warnings.append(&mut parser.warnings);
+                    let expr = expr?;
+                    if parser.has_next() {
+                        return Err(parser.unexpected(Expected::InterpolationEnd));
+                    }
+                    result.push(StringPart::Expression(expr));
+                }
+            }
+        }
+        Ok(ExpressionKind::Interpolated(result))
+    }
+
+    /// Parses statements up to the token that closes the current block
+    /// (`aage`, `warna`, `jab` or `pakdo`), leaving that token for the
+    /// caller.
+    /// Errors inside the block are recorded and parsing resumes at the next
+    /// statement.
     fn parse_block(&mut self) -> Vec<Statement> {
+        self.parse_block_with(Vec::new())
+    }
+
+    /// Parses a block whose scope starts with `names` already declared.
+    fn parse_block_with(&mut self, names: Vec<(String, Span)>) -> Vec<Statement> {
+        self.scopes.push(Vec::new());
+        for (name, span) in names {
+            self.declare(&name, span, false, None);
+        }
         let mut statements = Vec::new();
-        while self.current_token() != Token::BlockEnd {
-            if let Some(statement) = self.parse_statement() {
-                statements.push(statement);
+        while !matches!(
+            self.current_token(),
+            Token::BlockEnd | Token::Else | Token::Case | Token::Catch | Token::TheEnd
+        ) {
+            let start = self.current_token;
+            match self.parse_statement() {
+                Ok(statement) => statements.push(statement),
+                Err(error) => {
+                    self.errors.push(error);
+                    self.synchronize(start);
+                }
             }
+        }
+        self.scopes.pop();
+        statements
+    }
 
-            if self.current_token() == Token::Else {
-                break;
+    fn expect_block_end(&mut self, opened: Span) -> Result<(), ParseError> {
+        match self.current_token() {
+            Token::BlockEnd => {
+                self.next_token(); // aage
+                Ok(())
             }
+            Token::TheEnd => Err(ParseError::MissingBlockEnd {
+                opened,
+                span: self.current_span(),
+            }),
+            _ => Err(self.unexpected(Expected::Token(Token::BlockEnd))),
+        }
+    }
+}
 
-            if self.current_token() == Token::BlockEnd {
-                self.next_token();
-                break;
+/// The value of a number literal.
+fn number(expr: &Expression) -> Option<f64> {
+    match expr.kind {
+        ExpressionKind::Integer(i) => Some(i as f64),
+        ExpressionKind::Float(f) => Some(f),
+        _ => None,
+    }
+}
+
+/// Whether every value the `jab` case `later` matches is already matched
+/// by `earlier`.
+fn covers(earlier: &Case, later: &Case) -> bool {
+    match (earlier, later) {
+        (Case::Value(earlier), Case::Value(later)) => match (&earlier.kind, &later.kind) {
+            (ExpressionKind::Integer(a), ExpressionKind::Integer(b)) => a == b,
+            (ExpressionKind::String(a), ExpressionKind::String(b)) => a == b,
+            (ExpressionKind::Boolean(a), ExpressionKind::Boolean(b)) => a == b,
+            _ => number(earlier).is_some_and(|a| number(later) == Some(a)),
+        },
+        (Case::Range(start, end), Case::Value(value)) => number(value)
+            .is_some_and(|value| number(start) <= Some(value) && Some(value) <= number(end)),
+        (Case::Range(start, end), Case::Range(from, to)) => {
+            number(start) <= number(from) && number(to) <= number(end)
+        }
+        (Case::Value(_), Case::Range(..)) => false,
+    }
+}
+
+fn binary_operator(token: &Token) -> Option<BinOp> {
+    let op = match token {
+        Token::Plus => BinOp::Plus,
+        Token::Minus => BinOp::Minus,
+        Token::Multiply => BinOp::Multiply,
+        Token::Divide => BinOp::Divide,
+        Token::Modulo => BinOp::Modulo,
+        Token::LessThan => BinOp::LessThan,
+        Token::GreaterThan => BinOp::GreaterThan,
+        Token::LessThanOrEqual => BinOp::LessThanOrEqual,
+        Token::GreaterThanOrEqual => BinOp::GreaterThanOrEqual,
+        Token::Equals => BinOp::Equals,
+        Token::NotEquals => BinOp::NotEquals,
+        Token::Index => BinOp::Index,
+        Token::Contains => BinOp::Contains,
+        _ => return None,
+    };
+    Some(op)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Result<Program, Vec<ParseError>> {
+        let tokens = Token::tokenize(source).expect("source should lex");
+        Parser::new(&tokens).parse()
+    }
+
+    fn errors(source: &str) -> Vec<ParseError> {
+        parse(source).expect_err("source should not parse")
+    }
+
+    #[test]
+    fn statement_starting_with_an_identifier_is_reported() {
+        let errors = errors("x barabar 1\nlikho 2");
+        assert!(matches!(
+            errors[0],
+            ParseError::UnexpectedToken {
+                expected: Expected::Statement,
+                found: Token::Identifier(_),
+                ..
             }
+        ));
+    }
 
-            if self.current_token() == Token::Return {
-                break;
+    #[test]
+    fn stray_block_end_is_reported() {
+        let errors = errors("aage\nlikho 1\naage");
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| matches!(
+            error,
+            ParseError::UnexpectedToken {
+                expected: Expected::Statement,
+                found: Token::BlockEnd,
+                ..
             }
+        )));
+    }
+
+    #[test]
+    fn every_prefix_of_a_program_finishes_parsing() {
+        let source = "mano x barabar 1\n\
+                      agar x 0 se bada hai aur nhi x 5 hai\n\
+                      likho \"{x}\"\n\
+                      warna agar x 1 hai\n\
+                      badlo x barabar x 1 ka yog\n\
+                      aage\n\
+                      banao f jo le a b fir wapas karo a b ka guna aage\n\
+                      har i 1 se 3 tak chalao f i 2 par d me aage\n\
+                      mano l barabar suchi 1 2 aage\n\
+                      milao x jab 1 fir ruko jab 2 se 3 tak fir agla warna likho x aage\n\
+                      koshish fenko \"a\" pakdo e likho e ka sandesh aage";
+        let tokens = Token::tokenize(source).unwrap();
+        let (end, end_span) = tokens.last().unwrap().clone();
+        for length in 0..tokens.len() {
+            let mut prefix = tokens[..length].to_vec();
+            prefix.push((end.clone(), end_span));
+            let _ = Parser::new(&prefix).parse();
         }
-        statements
+    }
+
+    #[test]
+    fn recovery_reports_errors_from_later_statements() {
+        let errors = errors("likho 1 ka yog\nmano x barabar 2\nlikho\nlikho 3 4");
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(
+            errors[0],
+            ParseError::MissingOperand {
+                op: BinOp::Plus,
+                ..
+            }
+        ));
+        assert_eq!(errors[0].span().line, 1);
+        assert!(matches!(
+            errors[1],
+            ParseError::UnexpectedToken {
+                expected: Expected::Expression,
+                ..
+            }
+        ));
+        assert_eq!(errors[1].span().line, 4);
+        assert!(matches!(
+            errors[2],
+            ParseError::MissingOperator { count: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn errors_inside_a_block_do_not_hide_the_rest_of_it() {
+        let errors = errors(
+            "agar satya\n    likho ka yog\n    mano x barabar 1\n    mano x barabar 2\naage",
+        );
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].span().line, 2);
+        assert!(matches!(errors[1], ParseError::Redeclared { .. }));
+    }
+
+    #[test]
+    fn parameters_and_loop_variables_cannot_be_declared_again_in_their_body() {
+        for source in [
+            "banao f jo le a fir\nmano a barabar 2 aage",
+            "har i 1 se 3 tak\nmano i barabar 9 aage",
+            "har x suchi 1 aage me\nmano x barabar 9 aage",
+            "koshish fenko 1 pakdo e\nmano e barabar 2 aage",
+        ] {
+            let errors = errors(source);
+            match &errors[..] {
+                [ParseError::Redeclared { previous, span, .. }] => {
+                    assert_eq!((previous.line, span.line), (1, 2));
+                }
+                other => panic!("unexpected errors for {:?}: {:?}", source, other),
+            }
+        }
+    }
+
+    #[test]
+    fn function_names_cannot_be_declared_twice() {
+        for source in [
+            "banao f jo le x fir aage\nbanao f jo le y fir aage",
+            "mano f barabar 1\nbanao f jo le x fir aage",
+            "banao f jo le x fir aage\nmano f barabar 1",
+        ] {
+            let errors = errors(source);
+            match &errors[..] {
+                [ParseError::Redeclared {
+                    name,
+                    previous,
+                    span,
+                }] => {
+                    assert_eq!(name, "f");
+                    assert_eq!((previous.line, span.line), (1, 2));
+                }
+                other => panic!("unexpected errors for {:?}: {:?}", source, other),
+            }
+        }
+        assert!(parse(
+            "banao f jo le x fir banao g jo le y fir aage aage\nbanao g jo le z fir aage"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn unclosed_block_points_at_its_start() {
+        let errors = errors("likho 1\nagar satya\n    likho 2");
+        match &errors[..] {
+            [ParseError::MissingBlockEnd { opened, .. }] => assert_eq!(opened.line, 2),
+            other => panic!("unexpected errors: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn logical_operators_join_the_values_next_to_them_in_items() {
+        let program = parse("likho suchi 1 x 5 se bada hai ya nhi y aur z 2 aage").unwrap();
+        let StatementKind::Print(list) = &program.statements[0].kind else {
+            panic!("expected likho");
+        };
+        let ExpressionKind::List(items) = &list.kind else {
+            panic!("expected a suchi");
+        };
+        assert_eq!(items.len(), 3);
+        match &items[1].kind {
+            ExpressionKind::BinaryOp(_, BinOp::Or, right) => assert!(matches!(
+                right.kind,
+                ExpressionKind::BinaryOp(_, BinOp::And, _)
+            )),
+            other => panic!("unexpected item: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn logical_operator_without_a_value_after_it_is_reported() {
+        let errors = errors("banao f jo le x fir aage\nchalao f satya aur par");
+        assert!(matches!(
+            errors[..],
+            [ParseError::UnexpectedToken {
+                expected: Expected::Expression,
+                found: Token::FunctionReturn,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn case_equal_to_an_earlier_number_is_unreachable() {
+        let tokens = Token::tokenize("milao 1 jab 1 fir likho 1 jab 1.0 fir likho 2 aage").unwrap();
+        let mut parser = Parser::new(&tokens);
+        assert!(parser.parse().is_ok());
+        assert!(matches!(
+            parser.warnings(),
+            [ParseWarning::UnreachableCase { .. }]
+        ));
+    }
+
+    #[test]
+    fn valid_program_parses_without_errors() {
+        let program = parse("mano x barabar 1 2 ka yog\nlikho x").unwrap();
+        assert_eq!(program.statements.len(), 2);
+    }
+
+    /// The expression of a single `likho`, with each operation in brackets.
+    fn grouping(source: &str) -> String {
+        fn show(expr: &Expression) -> String {
+            match &expr.kind {
+                ExpressionKind::Identifier(name) => name.clone(),
+                ExpressionKind::Integer(value) => value.to_string(),
+                ExpressionKind::BinaryOp(left, op, right) => {
+                    format!("({} {:?} {})", show(left), op, show(right))
+                }
+                other => panic!("unexpected expression: {:?}", other),
+            }
+        }
+
+        let program = parse(source).unwrap();
+        match &program.statements[..] {
+            [Statement {
+                kind: StatementKind::Print(expr),
+                ..
+            }] => show(expr),
+            other => panic!("unexpected statements: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn chained_operations_group_from_the_left() {
+        assert_eq!(
+            grouping("likho a b ka antar c ka antar"),
+            "((a Minus b) Minus c)"
+        );
+        assert_eq!(
+            grouping("likho a b ka yog c ka guna"),
+            "((a Plus b) Multiply c)"
+        );
+        assert_eq!(grouping("likho 10 3 ka antar 2 ka antar"), "5");
+    }
+
+    #[test]
+    fn parentheses_group_an_operand() {
+        assert_eq!(
+            grouping("likho a (b c ka antar) ka antar"),
+            "(a Minus (b Minus c))"
+        );
+        assert_eq!(
+            grouping("likho (a b ka yog) (c d ka yog) ka guna"),
+            "((a Plus b) Multiply (c Plus d))"
+        );
+        assert_eq!(grouping("likho 10 (3 2 ka antar) ka antar"), "9");
     }
 }
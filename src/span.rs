@@ -0,0 +1,63 @@
+// src/span.rs
+
+use std::ops::Range;
+
+/// A location in the source file: a byte range plus the 1-based line and
+/// column (counted in characters) of its first byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    /// Joins two spans into one covering both, keeping the position of `self`.
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            start: self.start,
+            end: self.end.max(other.end),
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// Byte offsets of every line start, used to turn byte ranges into spans.
+#[derive(Debug, Clone, Default)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+        for (offset, ch) in source.char_indices() {
+            if ch == '\n' {
+                line_starts.push(offset + 1);
+            }
+        }
+        LineIndex { line_starts }
+    }
+
+    pub fn span(&self, source: &str, range: Range<usize>) -> Span {
+        let line = match self.line_starts.binary_search(&range.start) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let line_start = self.line_starts[line];
+        let column = source[line_start..range.start].chars().count() + 1;
+
+        Span {
+            start: range.start,
+            end: range.end,
+            line: line + 1,
+            column,
+        }
+    }
+}
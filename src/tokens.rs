@@ -1,92 +1,250 @@
-use logos::Logos;
+use logos::{Lexer, Logos};
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::span::{LineIndex, Span};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum LexErrorKind {
+    #[default]
+    UnexpectedCharacter,
+    UnterminatedString,
+    /// An unknown `\` escape, or a malformed `\u{...}`, inside a string.
+    InvalidEscape,
+    /// A `{` inside a string with no `}` closing it on the same line.
+    UnclosedInterpolation,
+    /// A numeric literal too large for `i64`, or a float that overflows.
+    NumberOutOfRange,
+    /// A `0x` or `0b` literal with no digits, or digits outside its base.
+    InvalidNumber,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexError {
+    pub kind: LexErrorKind,
+    pub text: String,
+    pub span: Span,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: ", self.span.line, self.span.column)?;
+        match self.kind {
+            LexErrorKind::UnexpectedCharacter => write!(f, "unexpected character `{}`", self.text),
+            LexErrorKind::UnterminatedString => write!(f, "unterminated string {}", self.text),
+            LexErrorKind::InvalidEscape => write!(f, "invalid escape `{}` in string", self.text),
+            LexErrorKind::UnclosedInterpolation => write!(f, "unclosed `{{` in string"),
+            LexErrorKind::NumberOutOfRange => write!(f, "number `{}` is out of range", self.text),
+            LexErrorKind::InvalidNumber => write!(f, "invalid number `{}`", self.text),
+        }
+    }
+}
+
+impl std::error::Error for LexError {}
+
+/// State shared by the lexer and its callbacks, so that string literals can
+/// lex their interpolated code with correct spans and report errors.
+#[derive(Debug, Default)]
+pub struct LexExtras {
+    index: LineIndex,
+    errors: Vec<LexError>,
+}
+
+/// A piece of an interpolated string: literal text, or the tokens of an
+/// embedded `{...}` expression followed by `Token::TheEnd`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TemplatePart {
+    Text(String),
+    Code(Vec<(Token, Span)>),
+}
 
 #[derive(Logos, Debug, PartialEq, Clone)]
+#[logos(error = LexErrorKind)]
+#[logos(extras = LexExtras)]
 pub enum Token {
+    // Every keyword also has a Devanagari spelling; words with a nukta are
+    // accepted both precomposed (ड़) and decomposed (ड + ़).
+
     // Keywords
     #[token("mano")]
+    #[token("मानो")]
     Let,
+    #[token("badlo")]
+    #[token("बदलो")]
+    Update,
+    #[token("sthir")]
+    #[token("स्थिर")]
+    Const,
     #[token("agar")]
+    #[token("अगर")]
     If,
     #[token("warna")]
+    #[token("वरना")]
     Else,
     #[token("jabtak")]
+    #[token("जबतक")]
+    #[token("जब तक")]
     While,
+    #[token("milao")]
+    #[token("मिलाओ")]
+    Match,
+    #[token("jab")]
+    #[token("जब")]
+    Case,
     #[token("aage")]
+    #[token("आगे")]
     BlockEnd,
+    #[token("har")]
+    #[token("हर")]
+    For,
+    #[token("se")]
+    #[token("से")]
+    From,
+    #[token("tak")]
+    #[token("तक")]
+    To,
+    #[token("kadam")]
+    #[token("कदम")]
+    Step,
+    #[token("ruko")]
+    #[token("रुको")]
+    Break,
+    #[token("agla")]
+    #[token("अगला")]
+    Continue,
     #[token(".")]
     StatementEnd,
     #[token("samapt")]
+    #[token("समाप्त")]
     TheEnd,
 
     //function
     #[token("banao")]
+    #[token("बनाओ")]
     FunctionDef,
     #[token("wapas karo")]
+    #[token("वापस करो")]
     Return,
     #[token("jo le")]
+    #[token("जो ले")]
     ParamStart,
     #[token("fir")]
+    #[token("फिर")]
     ParamEnd,
     #[token("chalao")]
+    #[token("चलाओ")]
     FunctionCallStart,
     #[token("par")]
+    #[token("पर")]
     FunctionReturn,
     #[token("me")]
+    #[token("में")]
     FunctionCallEnd,
 
-    // Literals
-    #[regex(r"[0-9]+", |lex| lex.slice().parse().ok())]
+    // Literals. Digits may be grouped with `_`, as in `1_00_000`.
+    #[regex(r"-?[0-9][0-9_]*", integer)]
+    #[regex(r"-?0[xX][0-9a-zA-Z_]*", integer)]
+    #[regex(r"-?0[bB][0-9a-zA-Z_]*", integer)]
     Integer(i64),
-    #[regex(r"[0-9]+\.[0-9]+", |lex| lex.slice().parse().ok())]
+    #[regex(
+        r"-?[0-9][0-9_]*(\.[0-9][0-9_]*([eE][+-]?[0-9]+)?|[eE][+-]?[0-9]+)",
+        float
+    )]
     Float(f64),
     #[token("satya")]
+    #[token("सत्य")]
     True,
     #[token("asatya")]
+    #[token("असत्य")]
     False,
-    #[regex(r#""[^"]*""#, |lex| Some(lex.slice().trim_matches('"').to_string()))]
     String(String),
+    /// A string containing `{...}` interpolations.
+    Template(Vec<TemplatePart>),
+    /// Opening quote of a string literal. Its callback lexes the whole
+    /// literal into `String` or `Template`, so this token is never produced.
+    #[token("\"", string_literal)]
+    DoubleQuote,
 
     // Identifiers
-    #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*", |lex| Some(lex.slice().to_string()))]
     Identifier(String),
+    /// A run of letters. Its callback produces `Identifier`, or the keyword
+    /// for `nhi`, `se`, `me` and `ka`: logos loses those matches when it
+    /// backtracks out of a failed `nhi hai`, `se bada hai`, `me hai` or
+    /// `ka yog`, so this token is never produced.
+    #[regex(r"[\p{L}_][\p{L}\p{M}\p{N}_]*", word)]
+    Word,
 
     // Operators
     #[token("ka yog")]
+    #[token("का योग")]
     Plus,
     #[token("ka antar")]
+    #[token("का अंतर")]
+    #[token("का अन्तर")]
     Minus,
     #[token("ka guna")]
+    #[token("का गुणा")]
     Multiply,
     #[token("ka bhaag")]
+    #[token("का भाग")]
     Divide,
     #[token("%")]
     Modulo,
+    #[token("ka tatva")]
+    #[token("का तत्व")]
+    Index,
+    #[token("ki lambai")]
+    #[token("की लंबाई")]
+    #[token("की लम्बाई")]
+    Length,
+    #[token("me hai")]
+    #[token("में है")]
+    Contains,
+    /// `ka`, `ki` or `ke` before a field name, as in `chhatra ka naam`.
+    #[token("ka")]
+    #[token("ki")]
+    #[token("ke")]
+    #[token("का")]
+    #[token("की")]
+    #[token("के")]
+    Of,
     #[token("barabar")]
+    #[token("बराबर")]
     Assign,
     #[token("hai")]
+    #[token("है")]
     Equals,
     #[token("nhi hai")]
+    #[token("नहीं है")]
     NotEquals,
     #[token("se chota hai")]
+    #[token("से छोटा है")]
     LessThan,
     #[token("se bada hai")]
+    #[token("से बड़ा है")]
+    #[token("से ब\u{95c}ा है")]
     GreaterThan,
     #[token("se chota hai ya barabar hai")]
+    #[token("से छोटा है या बराबर है")]
     LessThanOrEqual,
     #[token("se bada hai ya barabar hai")]
+    #[token("से बड़ा है या बराबर है")]
+    #[token("से ब\u{95c}ा है या बराबर है")]
     GreaterThanOrEqual,
     #[token("aur")]
+    #[token("और")]
     And,
     #[token("ya")]
+    #[token("या")]
     Or,
     #[token("nhi")]
+    #[token("नहीं")]
     Not,
 
     // Delimiters
     #[token(",")]
     Comma,
-    #[token("\"")]
-    DoubleQuote,
     #[token("'")]
     SingleQuote,
     #[token("(")]
@@ -99,22 +257,529 @@ pub enum Token {
     Whitespace,
 
     // Ignore comments
-    #[regex(r"faltu[^\n]*", logos::skip)]
+    #[regex(r"(faltu|फालतू|\u{95e}ालतू|फ\u{93c}ालतू)[^\n]*", logos::skip)]
     Comment,
 
     #[token("likho")]
+    #[token("लिखो")]
     Print,
     #[token("padho")]
+    #[token("पढ़ो")]
+    #[token("प\u{95d}ो")]
     Input,
+
+    // Lists
+    #[token("suchi")]
+    #[token("सूची")]
+    List,
+    #[token("kosh")]
+    #[token("कोश")]
+    #[token("कोष")]
+    Kosh,
+    #[token("jodo")]
+    #[token("जोड़ो")]
+    #[token("जो\u{95c}ो")]
+    Append,
+    #[token("hatao")]
+    #[token("हटाओ")]
+    Remove,
+
+    // Records
+    #[token("dhancha")]
+    #[token("ढांचा")]
+    #[token("ढाँचा")]
+    Struct,
+    #[token("naya")]
+    #[token("नया")]
+    New,
+
+    // Errors
+    #[token("koshish")]
+    #[token("कोशिश")]
+    Try,
+    #[token("pakdo")]
+    #[token("पकड़ो")]
+    #[token("पक\u{95c}ो")]
+    Catch,
+    #[token("fenko")]
+    #[token("फेंको")]
+    Throw,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Let => write!(f, "mano"),
+            Token::Update => write!(f, "badlo"),
+            Token::Const => write!(f, "sthir"),
+            Token::If => write!(f, "agar"),
+            Token::Else => write!(f, "warna"),
+            Token::While => write!(f, "jabtak"),
+            Token::Match => write!(f, "milao"),
+            Token::Case => write!(f, "jab"),
+            Token::BlockEnd => write!(f, "aage"),
+            Token::For => write!(f, "har"),
+            Token::From => write!(f, "se"),
+            Token::To => write!(f, "tak"),
+            Token::Step => write!(f, "kadam"),
+            Token::Break => write!(f, "ruko"),
+            Token::Continue => write!(f, "agla"),
+            Token::StatementEnd => write!(f, "."),
+            Token::TheEnd => write!(f, "samapt"),
+            Token::FunctionDef => write!(f, "banao"),
+            Token::Return => write!(f, "wapas karo"),
+            Token::ParamStart => write!(f, "jo le"),
+            Token::ParamEnd => write!(f, "fir"),
+            Token::FunctionCallStart => write!(f, "chalao"),
+            Token::FunctionReturn => write!(f, "par"),
+            Token::FunctionCallEnd => write!(f, "me"),
+            Token::Integer(value) => write!(f, "{}", value),
+            Token::Float(value) => write!(f, "{}", value),
+            Token::True => write!(f, "satya"),
+            Token::False => write!(f, "asatya"),
+            Token::String(value) => write!(f, "\"{}\"", value),
+            Token::Template(parts) => {
+                write!(f, "\"")?;
+                for part in parts {
+                    match part {
+                        TemplatePart::Text(text) => write!(f, "{}", text)?,
+                        TemplatePart::Code(_) => write!(f, "{{...}}")?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Token::DoubleQuote => write!(f, "\""),
+            Token::Identifier(name) => write!(f, "{}", name),
+            Token::Word => write!(f, "<word>"),
+            Token::Plus => write!(f, "ka yog"),
+            Token::Minus => write!(f, "ka antar"),
+            Token::Multiply => write!(f, "ka guna"),
+            Token::Divide => write!(f, "ka bhaag"),
+            Token::Modulo => write!(f, "%"),
+            Token::Index => write!(f, "ka tatva"),
+            Token::Length => write!(f, "ki lambai"),
+            Token::Contains => write!(f, "me hai"),
+            Token::Of => write!(f, "ka"),
+            Token::Assign => write!(f, "barabar"),
+            Token::Equals => write!(f, "hai"),
+            Token::NotEquals => write!(f, "nhi hai"),
+            Token::LessThan => write!(f, "se chota hai"),
+            Token::GreaterThan => write!(f, "se bada hai"),
+            Token::LessThanOrEqual => write!(f, "se chota hai ya barabar hai"),
+            Token::GreaterThanOrEqual => write!(f, "se bada hai ya barabar hai"),
+            Token::And => write!(f, "aur"),
+            Token::Or => write!(f, "ya"),
+            Token::Not => write!(f, "nhi"),
+            Token::Comma => write!(f, ","),
+            Token::SingleQuote => write!(f, "'"),
+            Token::OpenParen => write!(f, "("),
+            Token::CloseParen => write!(f, ")"),
+            Token::Whitespace => write!(f, " "),
+            Token::Comment => write!(f, "faltu"),
+            Token::Print => write!(f, "likho"),
+            Token::Input => write!(f, "padho"),
+            Token::List => write!(f, "suchi"),
+            Token::Kosh => write!(f, "kosh"),
+            Token::Append => write!(f, "jodo"),
+            Token::Remove => write!(f, "hatao"),
+            Token::Struct => write!(f, "dhancha"),
+            Token::New => write!(f, "naya"),
+            Token::Try => write!(f, "koshish"),
+            Token::Catch => write!(f, "pakdo"),
+            Token::Throw => write!(f, "fenko"),
+        }
+    }
+}
+
+/// Sorts a word into the keyword it spells or an identifier.
+fn word(lex: &mut Lexer<Token>) -> Token {
+    match lex.slice() {
+        "nhi" | "नहीं" => Token::Not,
+        "se" | "से" => Token::From,
+        "me" | "में" => Token::FunctionCallEnd,
+        "ka" | "ki" | "ke" | "का" | "की" | "के" => Token::Of,
+        "jab" | "जब" => Token::Case,
+        name => Token::Identifier(name.to_string()),
+    }
+}
+
+/// Parses a decimal, `0x` hexadecimal or `0b` binary integer literal.
+fn integer(lex: &mut Lexer<Token>) -> Result<i64, LexErrorKind> {
+    let slice = lex.slice();
+    let (sign, unsigned) = match slice.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", slice),
+    };
+    let (radix, digits) = if let Some(digits) = unsigned
+        .strip_prefix("0x")
+        .or_else(|| unsigned.strip_prefix("0X"))
+    {
+        (16, digits)
+    } else if let Some(digits) = unsigned
+        .strip_prefix("0b")
+        .or_else(|| unsigned.strip_prefix("0B"))
+    {
+        (2, digits)
+    } else {
+        (10, unsigned)
+    };
+
+    let digits: String = digits.chars().filter(|&c| c != '_').collect();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(LexErrorKind::InvalidNumber);
+    }
+    i64::from_str_radix(&format!("{}{}", sign, digits), radix)
+        .map_err(|_| LexErrorKind::NumberOutOfRange)
+}
+
+fn float(lex: &mut Lexer<Token>) -> Result<f64, LexErrorKind> {
+    let text: String = lex.slice().chars().filter(|&c| c != '_').collect();
+    match text.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(LexErrorKind::NumberOutOfRange),
+    }
+}
+
+/// Lexes a string literal after its opening quote, handling `\` escapes
+/// and `{...}` interpolations. Strings end at the next unescaped `"` on the
+/// same line; otherwise the rest of the line is skipped so lexing can carry
+/// on from the next one.
+fn string_literal(lex: &mut Lexer<Token>) -> Result<Token, LexErrorKind> {
+    let source = lex.source();
+    let start = lex.span().end;
+    let rest = &source[start..];
+
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut chars = rest.char_indices().peekable();
+
+    loop {
+        let Some((offset, ch)) = chars.next() else {
+            lex.bump(rest.len());
+            return Err(LexErrorKind::UnterminatedString);
+        };
+
+        match ch {
+            '"' => {
+                lex.bump(offset + 1);
+                break;
+            }
+            '\n' => {
+                lex.bump(offset);
+                return Err(LexErrorKind::UnterminatedString);
+            }
+            '\\' => {
+                let escaped = match chars.next() {
+                    Some((_, 'n')) => Some('\n'),
+                    Some((_, 't')) => Some('\t'),
+                    Some((_, 'r')) => Some('\r'),
+                    Some((_, '0')) => Some('\0'),
+                    Some((_, c @ ('"' | '\\' | '{' | '}'))) => Some(c),
+                    Some((_, 'u')) => unicode_escape(&rest[offset + 2..]).map(|(c, len)| {
+                        for _ in 0..len {
+                            chars.next();
+                        }
+                        c
+                    }),
+                    Some((newline, '\n')) => {
+                        lex.bump(newline);
+                        return Err(LexErrorKind::UnterminatedString);
+                    }
+                    None => {
+                        lex.bump(rest.len());
+                        return Err(LexErrorKind::UnterminatedString);
+                    }
+                    _ => None,
+                };
+                match escaped {
+                    Some(c) => text.push(c),
+                    None => {
+                        let end = chars.peek().map_or(rest.len(), |(next, _)| *next);
+                        let range = start + offset..start + end;
+                        lex.extras.errors.push(LexError {
+                            kind: LexErrorKind::InvalidEscape,
+                            text: source[range.clone()].to_string(),
+                            span: lex.extras.index.span(source, range),
+                        });
+                    }
+                }
+            }
+            '{' => {
+                let code_start = offset + 1;
+                let Some(code_end) = interpolation_end(&rest[code_start..]) else {
+                    let range = start + offset..start + code_start;
+                    lex.extras.errors.push(LexError {
+                        kind: LexErrorKind::UnclosedInterpolation,
+                        text: source[range.clone()].to_string(),
+                        span: lex.extras.index.span(source, range),
+                    });
+                    let line_end = rest.find('\n').unwrap_or(rest.len());
+                    lex.bump(line_end);
+                    return Ok(Token::String(text));
+                };
+                let code_end = code_start + code_end;
+                while chars.next_if(|(next, _)| *next <= code_end).is_some() {}
+
+                if !text.is_empty() {
+                    parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                }
+                let range = start + code_start..start + code_end;
+                parts.push(TemplatePart::Code(lex_range(
+                    source,
+                    range,
+                    &mut lex.extras,
+                )));
+            }
+            _ => text.push(ch),
+        }
+    }
+
+    if parts.is_empty() {
+        return Ok(Token::String(text));
+    }
+    if !text.is_empty() {
+        parts.push(TemplatePart::Text(text));
+    }
+    Ok(Token::Template(parts))
+}
+
+/// Parses the `{XXXX}` after `\u`, returning the character and the length of
+/// the braced part.
+fn unicode_escape(rest: &str) -> Option<(char, usize)> {
+    let hex = rest.strip_prefix('{')?;
+    let end = hex.find('}')?;
+    let code = u32::from_str_radix(&hex[..end], 16).ok()?;
+    Some((char::from_u32(code)?, end + 2))
+}
+
+/// Finds the `}` closing an interpolation, skipping nested braces and string
+/// literals. Returns `None` if the line ends first.
+fn interpolation_end(code: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut chars = code.char_indices();
+    while let Some((offset, ch)) = chars.next() {
+        match ch {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(offset),
+            '}' => depth -= 1,
+            '"' => loop {
+                match chars.next()?.1 {
+                    '"' => break,
+                    '\\' => {
+                        chars.next();
+                    }
+                    '\n' => return None,
+                    _ => {}
+                }
+            },
+            '\n' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Lexes `source[range]`, reporting errors into `extras`. The tokens end
+/// with `Token::TheEnd` at the end of the range.
+fn lex_range(source: &str, range: Range<usize>, extras: &mut LexExtras) -> Vec<(Token, Span)> {
+    let mut tokens: Vec<(Token, Span)> = vec![];
+    let mut lexer = Token::lexer_with_extras(&source[..range.end], std::mem::take(extras));
+    lexer.bump(range.start);
+
+    while let Some(token) = lexer.next() {
+        let range = lexer.span();
+        match token {
+            Ok(token) => {
+                let span = lexer.extras.index.span(source, range);
+                tokens.push((token, span));
+            }
+            Err(kind) => {
+                let errors = &mut lexer.extras.errors;
+                // Merge runs of unexpected characters into one error.
+                if let Some(last) = errors.last_mut() {
+                    if last.kind == LexErrorKind::UnexpectedCharacter
+                        && kind == LexErrorKind::UnexpectedCharacter
+                        && last.span.end == range.start
+                    {
+                        last.span.end = range.end;
+                        last.text.push_str(&source[range]);
+                        continue;
+                    }
+                }
+                let span = lexer.extras.index.span(source, range.clone());
+                lexer.extras.errors.push(LexError {
+                    kind,
+                    text: source[range].to_string(),
+                    span,
+                });
+            }
+        }
+    }
+
+    *extras = lexer.extras;
+    tokens.push((
+        Token::TheEnd,
+        extras.index.span(source, range.end..range.end),
+    ));
+    tokens
 }
 
 impl Token {
-    pub fn tokenize<'a>(source: &'a str) -> Vec<Token> {
-        let mut tokens: Vec<Token> = vec![];
-        let lexer = Token::lexer(source);
-        for token in lexer {
-            tokens.push(token.unwrap());
+    /// Whether this token is a keyword or operator phrase, as opposed to a
+    /// literal, a name or the end of input.
+    pub fn is_keyword(&self) -> bool {
+        !matches!(
+            self,
+            Token::Integer(_)
+                | Token::Float(_)
+                | Token::String(_)
+                | Token::Template(_)
+                | Token::Identifier(_)
+                | Token::Word
+                | Token::Whitespace
+                | Token::Comment
+                | Token::TheEnd
+        )
+    }
+
+    /// Splits `source` into tokens paired with their spans. The returned
+    /// stream always ends with `Token::TheEnd`.
+    ///
+    /// Lexing does not stop at the first bad character: every error in the
+    /// file is collected and returned together.
+    pub fn tokenize(source: &str) -> Result<Vec<(Token, Span)>, Vec<LexError>> {
+        let mut extras = LexExtras {
+            index: LineIndex::new(source),
+            errors: Vec::new(),
+        };
+        let tokens = lex_range(source, 0..source.len(), &mut extras);
+
+        if !extras.errors.is_empty() {
+            let mut errors = extras.errors;
+            errors.sort_by_key(|error| error.span.start);
+            return Err(errors);
         }
-        tokens
-    }    
-}
\ No newline at end of file
+
+        Ok(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(source: &str) -> Vec<Token> {
+        Token::tokenize(source)
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect()
+    }
+
+    fn error_kinds(source: &str) -> Vec<LexErrorKind> {
+        Token::tokenize(source)
+            .unwrap_err()
+            .into_iter()
+            .map(|error| error.kind)
+            .collect()
+    }
+
+    #[test]
+    fn bad_input_is_reported_and_lexing_continues() {
+        assert_eq!(
+            error_kinds("\"abc\nlikho 1 @"),
+            [
+                LexErrorKind::UnterminatedString,
+                LexErrorKind::UnexpectedCharacter,
+            ]
+        );
+    }
+
+    #[test]
+    fn spans_count_lines_and_columns() {
+        let tokens = Token::tokenize("likho 1\n  likho x").unwrap();
+        let (_, span) = &tokens[3];
+        assert_eq!((span.line, span.column), (2, 9));
+    }
+
+    #[test]
+    fn unknown_escape_is_reported() {
+        assert_eq!(error_kinds("\"\\q\""), [LexErrorKind::InvalidEscape]);
+    }
+
+    #[test]
+    fn escapes_are_replaced_by_the_characters_they_stand_for() {
+        assert_eq!(
+            tokens(r#""a\n\t\r\0\"\\\{\}\u{0905}""#),
+            [
+                Token::String("a\n\t\r\0\"\\{}\u{0905}".to_string()),
+                Token::TheEnd
+            ]
+        );
+    }
+
+    #[test]
+    fn interpolation_splits_a_string_into_text_and_code() {
+        match &tokens(r#""x = {x 1 ka yog}!""#)[..] {
+            [Token::Template(parts), Token::TheEnd] => match &parts[..] {
+                [TemplatePart::Text(before), TemplatePart::Code(code), TemplatePart::Text(after)] =>
+                {
+                    assert_eq!(before, "x = ");
+                    // `x`, `1`, `ka yog` and the end of the code.
+                    assert_eq!(code.len(), 4);
+                    assert_eq!(after, "!");
+                }
+                other => panic!("unexpected parts: {:?}", other),
+            },
+            other => panic!("unexpected tokens: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unclosed_interpolation_is_not_an_unterminated_string() {
+        let errors = Token::tokenize("likho \"a {b\"\nlikho 1").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, LexErrorKind::UnclosedInterpolation);
+        assert_eq!((errors[0].span.line, errors[0].span.column), (1, 10));
+    }
+
+    #[test]
+    fn numbers_in_every_base() {
+        assert_eq!(
+            tokens("-5 0xff 0b101 1_000 1.5e3"),
+            [
+                Token::Integer(-5),
+                Token::Integer(255),
+                Token::Integer(5),
+                Token::Integer(1000),
+                Token::Float(1500.0),
+                Token::TheEnd
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_numbers_are_reported() {
+        assert_eq!(
+            error_kinds("0x 99999999999999999999"),
+            [LexErrorKind::InvalidNumber, LexErrorKind::NumberOutOfRange,]
+        );
+    }
+
+    #[test]
+    fn nhi_before_another_word_is_not_an_identifier() {
+        assert_eq!(
+            tokens("nhi x"),
+            [Token::Not, Token::Identifier("x".into()), Token::TheEnd]
+        );
+        assert_eq!(tokens("nhi hai"), [Token::NotEquals, Token::TheEnd]);
+    }
+
+    #[test]
+    fn jab_before_another_word_is_not_an_identifier() {
+        assert_eq!(
+            tokens("जब x"),
+            [Token::Case, Token::Identifier("x".into()), Token::TheEnd]
+        );
+    }
+}